@@ -0,0 +1,245 @@
+// Fans a search out to several forges at once and merges what comes back
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{sort_repositories, BackendFuture, Forge, SearchBackend};
+use crate::error::SearchError;
+use crate::{
+    ForkFilter, RateLimitInfo, Repository, SearchFilters, SearchResponse, SearchResult, SortBy,
+};
+
+pub struct FederatedBackend {
+    name: String,
+    backends: Vec<Arc<dyn SearchBackend>>,
+}
+
+impl FederatedBackend {
+    pub fn new(backends: Vec<Arc<dyn SearchBackend>>) -> Self {
+        let name = backends
+            .iter()
+            .map(|b| b.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        FederatedBackend { name, backends }
+    }
+
+    /// Each forge contributes an equal share of a page, so merged pages stay close to the
+    /// requested size. Results are sorted within a page; pages aren't interleaved globally.
+    /// A forge whose share of the page lies past its result cap isn't asked at all.
+    async fn search_all(
+        &self,
+        filters: &SearchFilters,
+        refresh: bool,
+    ) -> Result<SearchResult, SearchError> {
+        let share = filters.per_page.div_ceil(self.backends.len().max(1) as u32);
+        let forge_filters = SearchFilters {
+            per_page: share,
+            ..filters.clone()
+        };
+
+        let offset = (filters.page.max(1) - 1).saturating_mul(share);
+        let past_cap = |backend: &Arc<dyn SearchBackend>| {
+            backend.max_results().is_some_and(|cap| offset >= cap)
+        };
+        let (asked, exhausted): (Vec<_>, Vec<_>) =
+            self.backends.iter().partition(|backend| !past_cap(backend));
+
+        let results = futures::future::join_all(asked.iter().map(|backend| {
+            if refresh {
+                backend.refresh(&forge_filters)
+            } else {
+                backend.search(&forge_filters)
+            }
+        }))
+        .await;
+
+        // A forge paged past its cap still counts for the cap, so the page count doesn't shrink
+        // under the pages still to come from the other forges
+        let mut total_count = exhausted
+            .iter()
+            .filter_map(|backend| backend.max_results())
+            .fold(0u32, u32::saturating_add);
+        let mut incomplete_results = false;
+        let mut rate_limit = None;
+        let mut items = Vec::new();
+        let mut warnings = Vec::new();
+        let mut failures = Vec::new();
+        let mut cached = true;
+
+        for (backend, result) in asked.iter().zip(results) {
+            match result {
+                Ok(result) => {
+                    // Forges cap differently; count only what could actually be paged through
+                    let reachable = result
+                        .response
+                        .total_count
+                        .min(backend.max_results().unwrap_or(u32::MAX));
+                    total_count = total_count.saturating_add(reachable);
+                    incomplete_results |= result.response.incomplete_results;
+                    rate_limit = rate_limit.or(result.rate_limit);
+                    items.extend(result.response.items);
+                    warnings.extend(result.warnings);
+                    cached &= result.cached;
+                }
+                Err(e) => failures.push((backend.name(), e)),
+            }
+        }
+
+        // Only a search that failed everywhere is an error, reported as the first forge's
+        if !asked.is_empty() && failures.len() == asked.len() {
+            return Err(failures.remove(0).1);
+        }
+        warnings.extend(
+            failures
+                .into_iter()
+                .map(|(name, e)| format!("Results are missing from {}: {}", name, e)),
+        );
+
+        let mut items = dedupe_mirrors(items);
+        sort_repositories(&mut items, filters.sort_by, filters.sort_order);
+
+        Ok(SearchResult {
+            response: SearchResponse {
+                total_count,
+                incomplete_results,
+                items,
+            },
+            rate_limit,
+            warnings,
+            cached,
+        })
+    }
+
+    /// Tries each forge in turn, failing with the first forge's error if none has the repository
+    async fn fetch_first(&self, full_name: &str) -> Result<Repository, SearchError> {
+        let mut first_error = None;
+        for backend in &self.backends {
+            match backend.fetch_repository(full_name).await {
+                Ok(repo) => return Ok(repo),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        Err(first_error.unwrap_or(SearchError::Http(404)))
+    }
+
+    /// Asks every forge, reporting the first quota any of them has. A forge that fails doesn't
+    /// hide the others' quotas; its error is only returned when no forge reported one.
+    async fn first_rate_limit(&self) -> Result<Option<RateLimitInfo>, SearchError> {
+        let results =
+            futures::future::join_all(self.backends.iter().map(|backend| backend.rate_limit()))
+                .await;
+
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(Some(rate_limit)) => return Ok(Some(rate_limit)),
+                Ok(None) => {}
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(None), Err)
+    }
+}
+
+/// Identifies a repository across forges by its normalized URL path, since mirrors usually
+/// keep the owner and name of the original
+fn mirror_key(repo: &Repository) -> String {
+    let url = repo
+        .html_url
+        .split_once("://")
+        .map_or(repo.html_url.as_str(), |(_, rest)| rest);
+    let path = url
+        .split_once('/')
+        .map_or(repo.full_name.as_str(), |(_, path)| path);
+    path.trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_lowercase()
+}
+
+/// Keeps one copy of each mirrored repository, preferring the most starred (usually the upstream)
+fn dedupe_mirrors(items: Vec<Repository>) -> Vec<Repository> {
+    let mut kept: Vec<Repository> = Vec::with_capacity(items.len());
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for repo in items {
+        let key = mirror_key(&repo);
+        match index.get(&key) {
+            Some(&i) if kept[i].stargazers_count >= repo.stargazers_count => {}
+            Some(&i) => kept[i] = repo,
+            None => {
+                index.insert(key, kept.len());
+                kept.push(repo);
+            }
+        }
+    }
+    kept
+}
+
+impl SearchBackend for FederatedBackend {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn forge(&self) -> Forge {
+        // Results carry their own forge; this is only the fallback for untagged ones
+        self.backends.first().map(|b| b.forge()).unwrap_or_default()
+    }
+
+    fn web_url(&self) -> String {
+        self.backends
+            .first()
+            .map(|b| b.web_url())
+            .unwrap_or_default()
+    }
+
+    fn authenticated(&self) -> bool {
+        // Searches are only authenticated throughout when every forge's are
+        self.backends.iter().all(|b| b.authenticated())
+    }
+
+    fn max_per_page(&self) -> u32 {
+        // Each forge gets an equal share of a page, which the smallest limit bounds
+        let smallest = self.backends.iter().map(|b| b.max_per_page()).min();
+        smallest.map_or(1, |smallest| smallest * self.backends.len() as u32)
+    }
+
+    fn supports_sort(&self, sort_by: SortBy) -> bool {
+        self.backends.iter().all(|b| b.supports_sort(sort_by))
+    }
+
+    fn supports_fork_filter(&self, fork_filter: ForkFilter) -> bool {
+        self.backends
+            .iter()
+            .all(|b| b.supports_fork_filter(fork_filter))
+    }
+
+    fn search<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>> {
+        Box::pin(self.search_all(filters, false))
+    }
+
+    fn refresh<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>> {
+        Box::pin(self.search_all(filters, true))
+    }
+
+    fn fetch_repository<'a>(
+        &'a self,
+        full_name: &'a str,
+    ) -> BackendFuture<'a, Result<Repository, SearchError>> {
+        Box::pin(self.fetch_first(full_name))
+    }
+
+    fn rate_limit(&self) -> BackendFuture<'_, Result<Option<RateLimitInfo>, SearchError>> {
+        Box::pin(self.first_rate_limit())
+    }
+}
@@ -0,0 +1,226 @@
+// Gitea API backend, which also serves Forgejo instances such as Codeberg
+
+use serde::Deserialize;
+
+use super::{matches_filters, BackendFuture, Forge, SearchBackend};
+use crate::error::{error_from_response, SearchError};
+use crate::{
+    urlencoding, ArchivedFilter, ForkFilter, Owner, RateLimitInfo, Repository, SearchFilters,
+    SearchResponse, SearchResult, SortBy,
+};
+
+pub const CODEBERG_URL: &str = "https://codeberg.org";
+
+#[derive(Deserialize)]
+struct GiteaSearchResponse {
+    data: Vec<GiteaRepository>,
+}
+
+#[derive(Deserialize)]
+struct GiteaRepository {
+    id: u64,
+    name: String,
+    full_name: String,
+    html_url: String,
+    description: Option<String>,
+    language: Option<String>,
+    #[serde(default)]
+    stars_count: u32,
+    #[serde(default)]
+    forks_count: u32,
+    #[serde(default)]
+    open_issues_count: u32,
+    created_at: String,
+    updated_at: String,
+    owner: GiteaOwner,
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    archived: bool,
+    topics: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct GiteaOwner {
+    login: String,
+    #[serde(default)]
+    avatar_url: String,
+}
+
+impl From<GiteaRepository> for Repository {
+    fn from(repo: GiteaRepository) -> Self {
+        Repository {
+            id: repo.id,
+            name: repo.name,
+            full_name: repo.full_name,
+            html_url: repo.html_url,
+            // Gitea reports missing descriptions and undetected languages as ""
+            description: repo.description.filter(|d| !d.is_empty()),
+            language: repo.language.filter(|l| !l.is_empty()),
+            stargazers_count: repo.stars_count,
+            forks_count: repo.forks_count,
+            open_issues_count: repo.open_issues_count,
+            created_at: repo.created_at,
+            updated_at: repo.updated_at,
+            owner: Owner {
+                login: repo.owner.login,
+                avatar_url: repo.owner.avatar_url,
+            },
+            fork: repo.fork,
+            archived: repo.archived,
+            topics: repo.topics,
+            forge: Some(Forge::Gitea),
+        }
+    }
+}
+
+pub struct GiteaBackend {
+    base_url: String,
+    token: Option<String>,
+}
+
+impl GiteaBackend {
+    pub fn new(base_url: &str, token: Option<String>) -> Self {
+        GiteaBackend {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    fn request(&self, url: &str) -> reqwasm::http::Request {
+        let request = reqwasm::http::Request::get(url).header("Accept", "application/json");
+        match &self.token {
+            Some(token) => request.header("Authorization", &format!("token {}", token)),
+            None => request,
+        }
+    }
+
+    fn search_url(&self, filters: &SearchFilters) -> String {
+        let sort = match filters.sort_by {
+            SortBy::Stars | SortBy::Issues => "stars",
+            SortBy::Forks => "forks",
+            SortBy::Created => "created",
+            SortBy::Updated => "updated",
+        };
+
+        let mut url = format!(
+            "{}/api/v1/repos/search?q={}&sort={}&order={}&page={}&limit={}",
+            self.base_url,
+            urlencoding(&filters.query),
+            sort,
+            filters.sort_order.as_str(),
+            filters.page,
+            // Larger limits are silently capped, which would shift every later page
+            filters.per_page.min(self.max_per_page())
+        );
+        match filters.archived_filter {
+            ArchivedFilter::All => {}
+            ArchivedFilter::ActiveOnly => url.push_str("&archived=false"),
+            ArchivedFilter::ArchivedOnly => url.push_str("&archived=true"),
+        }
+        match filters.fork_filter {
+            ForkFilter::All => {}
+            ForkFilter::OriginalOnly => url.push_str("&mode=source"),
+            ForkFilter::ForksOnly => url.push_str("&mode=fork"),
+        }
+        url
+    }
+
+    async fn search_repositories(
+        &self,
+        filters: &SearchFilters,
+    ) -> Result<SearchResult, SearchError> {
+        let response = self.request(&self.search_url(filters)).send().await?;
+
+        if !response.ok() {
+            return Err(error_from_response(response).await);
+        }
+
+        // Gitea reports the total in a header rather than the response body
+        let total_count = response
+            .headers()
+            .get("x-total-count")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        let body = response.json::<GiteaSearchResponse>().await?;
+
+        let items = body
+            .data
+            .into_iter()
+            .map(Repository::from)
+            .filter(|repo| matches_filters(repo, filters))
+            .collect();
+
+        Ok(SearchResult {
+            response: SearchResponse {
+                total_count,
+                incomplete_results: false,
+                items,
+            },
+            rate_limit: None,
+            warnings: Vec::new(),
+            cached: false,
+        })
+    }
+
+    async fn get_repository(&self, full_name: &str) -> Result<Repository, SearchError> {
+        let url = format!("{}/api/v1/repos/{}", self.base_url, full_name);
+        let response = self.request(&url).send().await?;
+
+        if !response.ok() {
+            return Err(error_from_response(response).await);
+        }
+        Ok(response.json::<GiteaRepository>().await?.into())
+    }
+}
+
+impl SearchBackend for GiteaBackend {
+    fn name(&self) -> &str {
+        if self.base_url == CODEBERG_URL {
+            "Codeberg"
+        } else {
+            "Gitea"
+        }
+    }
+
+    fn forge(&self) -> Forge {
+        Forge::Gitea
+    }
+
+    fn web_url(&self) -> String {
+        self.base_url.clone()
+    }
+
+    fn authenticated(&self) -> bool {
+        self.token.is_some()
+    }
+
+    fn max_per_page(&self) -> u32 {
+        // Gitea's default MAX_RESPONSE_ITEMS, which Codeberg keeps
+        50
+    }
+
+    fn supports_sort(&self, sort_by: SortBy) -> bool {
+        sort_by != SortBy::Issues
+    }
+
+    fn search<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>> {
+        Box::pin(self.search_repositories(filters))
+    }
+
+    fn fetch_repository<'a>(
+        &'a self,
+        full_name: &'a str,
+    ) -> BackendFuture<'a, Result<Repository, SearchError>> {
+        Box::pin(self.get_repository(full_name))
+    }
+
+    fn rate_limit(&self) -> BackendFuture<'_, Result<Option<RateLimitInfo>, SearchError>> {
+        // Gitea doesn't rate limit its API by default
+        Box::pin(async { Ok(None) })
+    }
+}
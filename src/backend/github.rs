@@ -0,0 +1,319 @@
+// GitHub REST API backend
+
+use serde::Deserialize;
+
+use super::{BackendFuture, Forge, SearchBackend};
+use crate::cache;
+use crate::error::{error_from_response, SearchError};
+use crate::{
+    build_search_query, urlencoding, RateLimitInfo, Repository, SearchFilters, SearchResponse,
+    SearchResult,
+};
+
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// The API base URL baked in at build time through `OSS_EXPLORER_API_URL`, or github.com's
+pub fn default_api_url() -> String {
+    normalize_api_url(option_env!("OSS_EXPLORER_API_URL").unwrap_or(GITHUB_API_URL))
+}
+
+/// A search proxy (`oss-explorer serve`) baked in at build time through `OSS_EXPLORER_SEARCH_URL`;
+/// `/` means the origin serving the app
+pub fn default_search_url() -> Option<String> {
+    option_env!("OSS_EXPLORER_SEARCH_URL")
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+}
+
+/// Trims trailing slashes and, for a bare GitHub Enterprise host, adds the `/api/v3` prefix
+pub fn normalize_api_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let host_start = url.find("://").map(|i| i + 3).unwrap_or(0);
+    let has_path = url[host_start..].contains('/');
+
+    if has_path || url == GITHUB_API_URL {
+        url.to_string()
+    } else {
+        format!("{}/api/v3", url)
+    }
+}
+
+/// The web UI root matching an API base URL: api.github.com serves github.com,
+/// while GitHub Enterprise serves its API under `/api/v3` on the web host
+pub fn web_url_for_api(api_url: &str) -> String {
+    if api_url == GITHUB_API_URL {
+        return "https://github.com".to_string();
+    }
+    api_url
+        .strip_suffix("/api/v3")
+        .unwrap_or(api_url)
+        .to_string()
+}
+
+pub(crate) const JSON_ACCEPT: &str = "application/vnd.github.v3+json";
+
+/// Search never returns more than the first this many results
+pub(crate) const MAX_RESULTS: u32 = 1000;
+
+/// Largest page of search results the API returns
+pub(crate) const MAX_PER_PAGE: u32 = 100;
+
+/// Builds a GET request against the GitHub API with our standard headers and the optional token
+pub(crate) fn github_request(
+    url: &str,
+    accept: &str,
+    token: Option<&str>,
+) -> reqwasm::http::Request {
+    let request = reqwasm::http::Request::get(url)
+        .header("Accept", accept)
+        .header("User-Agent", "oss-explorer");
+
+    match token {
+        Some(token) => request.header("Authorization", &format!("Bearer {}", token)),
+        None => request,
+    }
+}
+
+#[derive(Deserialize)]
+struct RateLimitResponse {
+    resources: RateLimitResources,
+}
+
+#[derive(Deserialize)]
+struct RateLimitResources {
+    search: RateLimitResource,
+}
+
+#[derive(Deserialize)]
+struct RateLimitResource {
+    limit: u32,
+    remaining: u32,
+    reset: u64,
+}
+
+pub struct GithubBackend {
+    api_url: String,
+    /// Where searches and the search quota are requested: the API itself, or a search proxy
+    search_api_url: String,
+    token: Option<String>,
+}
+
+impl GithubBackend {
+    pub fn new(api_url: impl Into<String>, token: Option<String>) -> Self {
+        let api_url = api_url.into();
+        // The proxy searches the build's API with its own token, so anyone who brings their
+        // own token or points the app at another instance searches directly
+        let search_api_url = default_search_url()
+            .filter(|_| token.is_none() && api_url == default_api_url())
+            .unwrap_or_else(|| api_url.clone());
+        GithubBackend {
+            api_url,
+            search_api_url,
+            token,
+        }
+    }
+
+    fn is_enterprise(&self) -> bool {
+        self.api_url != GITHUB_API_URL
+    }
+
+    fn request(&self, url: &str) -> reqwasm::http::Request {
+        github_request(url, JSON_ACCEPT, self.token.as_deref())
+    }
+
+    /// Searches through the response cache: fresh entries are served as they are, stale ones
+    /// are revalidated with `If-None-Match`, and a 304 doesn't count against the rate limit.
+    /// `use_cache: false` still stores the response, it just never reads the cache.
+    async fn search_repositories(
+        &self,
+        filters: &SearchFilters,
+        use_cache: bool,
+    ) -> Result<SearchResult, SearchError> {
+        let url = search_url(&self.search_api_url, filters);
+        let cache_key = cache::key(&url, self.token.as_deref());
+
+        let cached = if use_cache {
+            cache::lookup(&cache_key).await
+        } else {
+            None
+        };
+        if let Some(entry) = cached.as_ref().filter(|entry| entry.is_fresh()) {
+            return search_result(&entry.body, None, true);
+        }
+
+        let mut request = self.request(&url);
+        if let Some(etag) = cached.as_ref().and_then(|entry| entry.etag.as_deref()) {
+            request = request.header("If-None-Match", etag);
+        }
+        let response = request.send().await?;
+
+        // Extract rate limit headers
+        let headers = response.headers();
+        let rate_limit = rate_limit_from_headers(|name| headers.get(name));
+
+        if response.status() == 304 {
+            if let Some(entry) = cached {
+                cache::store(&cache_key, entry.etag, entry.body.clone());
+                return search_result(&entry.body, rate_limit, true);
+            }
+        }
+        if !response.ok() {
+            return Err(error_from_response(response).await);
+        }
+
+        let etag = response.headers().get("etag");
+        let body = response.text().await?;
+        let result = search_result(&body, rate_limit, false)?;
+        cache::store(&cache_key, etag, body);
+        Ok(result)
+    }
+
+    async fn get_repository(&self, full_name: &str) -> Result<Repository, SearchError> {
+        let url = format!("{}/repos/{}", self.api_url, full_name);
+        let response = self.request(&url).send().await?;
+
+        if !response.ok() {
+            return Err(error_from_response(response).await);
+        }
+        let repo = response.json::<Repository>().await?;
+        Ok(Repository {
+            forge: Some(Forge::Github),
+            ..repo
+        })
+    }
+
+    async fn get_rate_limit(&self) -> Result<Option<RateLimitInfo>, SearchError> {
+        let url = format!("{}/rate_limit", self.search_api_url);
+        let response = self.request(&url).send().await?;
+
+        // GitHub Enterprise answers 404 when rate limiting is disabled
+        if response.status() == 404 {
+            return Ok(None);
+        }
+        if !response.ok() {
+            return Err(error_from_response(response).await);
+        }
+
+        let body = response.json::<RateLimitResponse>().await?;
+        let search = body.resources.search;
+
+        Ok(Some(RateLimitInfo {
+            limit: search.limit,
+            remaining: search.remaining,
+            reset_timestamp: search.reset,
+        }))
+    }
+}
+
+impl SearchBackend for GithubBackend {
+    fn name(&self) -> &str {
+        if self.is_enterprise() {
+            "GitHub Enterprise"
+        } else {
+            "GitHub"
+        }
+    }
+
+    fn forge(&self) -> Forge {
+        Forge::Github
+    }
+
+    fn web_url(&self) -> String {
+        web_url_for_api(&self.api_url)
+    }
+
+    fn authenticated(&self) -> bool {
+        self.token.is_some()
+    }
+
+    fn max_results(&self) -> Option<u32> {
+        Some(MAX_RESULTS)
+    }
+
+    fn max_per_page(&self) -> u32 {
+        MAX_PER_PAGE
+    }
+
+    fn search<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>> {
+        Box::pin(self.search_repositories(filters, true))
+    }
+
+    fn refresh<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>> {
+        Box::pin(self.search_repositories(filters, false))
+    }
+
+    fn fetch_repository<'a>(
+        &'a self,
+        full_name: &'a str,
+    ) -> BackendFuture<'a, Result<Repository, SearchError>> {
+        Box::pin(self.get_repository(full_name))
+    }
+
+    fn rate_limit(&self) -> BackendFuture<'_, Result<Option<RateLimitInfo>, SearchError>> {
+        Box::pin(self.get_rate_limit())
+    }
+}
+
+/// The search URL for one page of results; with the token, also the response cache key
+pub(crate) fn search_url(api_url: &str, filters: &SearchFilters) -> String {
+    format!(
+        "{}/search/repositories?q={}&sort={}&order={}&per_page={}&page={}",
+        api_url,
+        urlencoding(&build_search_query(filters)),
+        filters.sort_by.as_str(),
+        filters.sort_order.as_str(),
+        filters.per_page,
+        filters.page
+    )
+}
+
+/// Parses a search response body, live or cached
+pub(crate) fn search_result(
+    body: &str,
+    rate_limit: Option<RateLimitInfo>,
+    cached: bool,
+) -> Result<SearchResult, SearchError> {
+    let mut response: SearchResponse =
+        serde_json::from_str(body).map_err(|e| SearchError::Decode(e.to_string()))?;
+    for repo in &mut response.items {
+        repo.forge = Some(Forge::Github);
+    }
+
+    Ok(SearchResult {
+        response,
+        rate_limit,
+        warnings: Vec::new(),
+        cached,
+    })
+}
+
+/// Reads GitHub's `x-ratelimit-*` headers through `header`, which looks one up by name
+pub(crate) fn rate_limit_from_headers(
+    header: impl Fn(&str) -> Option<String>,
+) -> Option<RateLimitInfo> {
+    let limit = header("x-ratelimit-limit")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let remaining = header("x-ratelimit-remaining")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let reset_timestamp = header("x-ratelimit-reset")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+
+    if limit > 0 {
+        Some(RateLimitInfo {
+            limit,
+            remaining,
+            reset_timestamp,
+        })
+    } else {
+        None
+    }
+}
@@ -0,0 +1,279 @@
+// GitLab REST API backend, for gitlab.com and self-hosted instances
+
+use serde::Deserialize;
+
+use super::{matches_filters, BackendFuture, Forge, SearchBackend};
+use crate::error::{error_from_response, SearchError};
+use crate::{
+    urlencoding, ArchivedFilter, ForkFilter, Owner, RateLimitInfo, Repository, SearchFilters,
+    SearchResponse, SearchResult, SortBy,
+};
+
+pub const GITLAB_URL: &str = "https://gitlab.com";
+
+#[derive(Deserialize)]
+struct GitlabProject {
+    id: u64,
+    name: String,
+    path_with_namespace: String,
+    web_url: String,
+    description: Option<String>,
+    #[serde(default)]
+    star_count: u32,
+    #[serde(default)]
+    forks_count: u32,
+    // Absent when the project has issues disabled
+    #[serde(default)]
+    open_issues_count: u32,
+    created_at: String,
+    last_activity_at: String,
+    namespace: GitlabNamespace,
+    avatar_url: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    // Only present on forks
+    #[serde(default)]
+    forked_from_project: Option<serde_json::Value>,
+    #[serde(default)]
+    archived: bool,
+}
+
+#[derive(Deserialize)]
+struct GitlabNamespace {
+    full_path: String,
+    avatar_url: Option<String>,
+}
+
+pub struct GitlabBackend {
+    base_url: String,
+    token: Option<String>,
+}
+
+impl GitlabBackend {
+    pub fn new(base_url: &str, token: Option<String>) -> Self {
+        GitlabBackend {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    fn request(&self, url: &str) -> reqwasm::http::Request {
+        let request = reqwasm::http::Request::get(url).header("Accept", "application/json");
+        match &self.token {
+            Some(token) => request.header("PRIVATE-TOKEN", token),
+            None => request,
+        }
+    }
+
+    /// Self-hosted instances return avatars relative to the instance root
+    fn absolute_url(&self, url: &str) -> String {
+        if url.starts_with('/') {
+            format!("{}{}", self.base_url, url)
+        } else {
+            url.to_string()
+        }
+    }
+
+    fn to_repository(&self, project: GitlabProject, language: Option<String>) -> Repository {
+        let avatar = project
+            .namespace
+            .avatar_url
+            .or(project.avatar_url)
+            .map(|url| self.absolute_url(&url))
+            .unwrap_or_default();
+
+        Repository {
+            id: project.id,
+            name: project.name,
+            full_name: project.path_with_namespace,
+            html_url: project.web_url,
+            description: project.description,
+            language,
+            stargazers_count: project.star_count,
+            forks_count: project.forks_count,
+            open_issues_count: project.open_issues_count,
+            created_at: project.created_at,
+            updated_at: project.last_activity_at,
+            owner: Owner {
+                login: project.namespace.full_path,
+                avatar_url: avatar,
+            },
+            fork: project.forked_from_project.is_some(),
+            archived: project.archived,
+            topics: Some(project.topics),
+            forge: Some(Forge::Gitlab),
+        }
+    }
+
+    fn search_url(&self, filters: &SearchFilters) -> String {
+        let order_by = match filters.sort_by {
+            SortBy::Created => "created_at",
+            SortBy::Updated => "last_activity_at",
+            // GitLab can't order by forks or issues; star count is the closest ranking
+            SortBy::Stars | SortBy::Forks | SortBy::Issues => "star_count",
+        };
+
+        let mut url = format!(
+            "{}/api/v4/projects?order_by={}&sort={}&per_page={}&page={}",
+            self.base_url,
+            order_by,
+            filters.sort_order.as_str(),
+            filters.per_page,
+            filters.page
+        );
+        if !filters.query.is_empty() {
+            url.push_str(&format!("&search={}", urlencoding(&filters.query)));
+        }
+        if filters.language != "All" && !filters.language.is_empty() {
+            url.push_str(&format!(
+                "&with_programming_language={}",
+                urlencoding(&filters.language)
+            ));
+        }
+        if !filters.topics.is_empty() {
+            url.push_str(&format!(
+                "&topic={}",
+                urlencoding(&filters.topics.join(","))
+            ));
+        }
+        match filters.archived_filter {
+            ArchivedFilter::All => {}
+            ArchivedFilter::ActiveOnly => url.push_str("&archived=false"),
+            ArchivedFilter::ArchivedOnly => url.push_str("&archived=true"),
+        }
+        if !filters.pushed_from.is_empty() {
+            url.push_str(&format!("&last_activity_after={}", filters.pushed_from));
+        }
+        if !filters.pushed_to.is_empty() {
+            url.push_str(&format!("&last_activity_before={}", filters.pushed_to));
+        }
+        url
+    }
+
+    async fn search_projects(&self, filters: &SearchFilters) -> Result<SearchResult, SearchError> {
+        let response = self.request(&self.search_url(filters)).send().await?;
+
+        let rate_limit = extract_rate_limit_info(&response);
+
+        if !response.ok() {
+            return Err(error_from_response(response).await);
+        }
+
+        // x-total is omitted for very large result sets; assume at least one more page then
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.parse::<u32>().ok())
+        };
+        let total_count = header("x-total").unwrap_or_else(|| {
+            let pages = filters.page + u32::from(header("x-next-page").is_some());
+            pages * filters.per_page
+        });
+
+        let projects = response.json::<Vec<GitlabProject>>().await?;
+
+        // GitLab only returns projects in the requested language, but doesn't say which it is
+        let language = Some(filters.language.clone()).filter(|l| l != "All" && !l.is_empty());
+        let items = projects
+            .into_iter()
+            .map(|p| self.to_repository(p, language.clone()))
+            .filter(|repo| matches_filters(repo, filters))
+            .collect();
+
+        Ok(SearchResult {
+            response: SearchResponse {
+                total_count,
+                incomplete_results: false,
+                items,
+            },
+            rate_limit,
+            warnings: Vec::new(),
+            cached: false,
+        })
+    }
+
+    async fn get_project(&self, full_name: &str) -> Result<Repository, SearchError> {
+        let url = format!(
+            "{}/api/v4/projects/{}",
+            self.base_url,
+            urlencoding(full_name)
+        );
+        let response = self.request(&url).send().await?;
+
+        if !response.ok() {
+            return Err(error_from_response(response).await);
+        }
+        let project = response.json::<GitlabProject>().await?;
+        Ok(self.to_repository(project, None))
+    }
+}
+
+impl SearchBackend for GitlabBackend {
+    fn name(&self) -> &str {
+        "GitLab"
+    }
+
+    fn forge(&self) -> Forge {
+        Forge::Gitlab
+    }
+
+    fn web_url(&self) -> String {
+        self.base_url.clone()
+    }
+
+    fn authenticated(&self) -> bool {
+        self.token.is_some()
+    }
+
+    fn max_per_page(&self) -> u32 {
+        // GitLab's per_page limit
+        100
+    }
+
+    fn supports_sort(&self, sort_by: SortBy) -> bool {
+        !matches!(sort_by, SortBy::Forks | SortBy::Issues)
+    }
+
+    fn supports_fork_filter(&self, fork_filter: ForkFilter) -> bool {
+        // Forks only is filtered client-side, which leaves most pages nearly empty
+        fork_filter != ForkFilter::ForksOnly
+    }
+
+    fn search<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>> {
+        Box::pin(self.search_projects(filters))
+    }
+
+    fn fetch_repository<'a>(
+        &'a self,
+        full_name: &'a str,
+    ) -> BackendFuture<'a, Result<Repository, SearchError>> {
+        Box::pin(self.get_project(full_name))
+    }
+
+    fn rate_limit(&self) -> BackendFuture<'_, Result<Option<RateLimitInfo>, SearchError>> {
+        // GitLab has no quota endpoint; limits are only reported alongside searches
+        Box::pin(async { Ok(None) })
+    }
+}
+
+fn extract_rate_limit_info(response: &reqwasm::http::Response) -> Option<RateLimitInfo> {
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+
+    match (header("ratelimit-limit"), header("ratelimit-remaining")) {
+        (Some(limit), Some(remaining)) if limit > 0 => Some(RateLimitInfo {
+            limit: limit as u32,
+            remaining: remaining as u32,
+            reset_timestamp: header("ratelimit-reset").unwrap_or(0),
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,278 @@
+// Search backends: the forges (or proxies, or fakes) the app can search
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::SearchError;
+use crate::{
+    storage, ForkFilter, RateLimitInfo, Repository, SearchFilters, SearchResult, SortBy, SortOrder,
+};
+
+pub mod federated;
+pub mod gitea;
+pub mod github;
+pub mod gitlab;
+
+pub use federated::FederatedBackend;
+pub use gitea::GiteaBackend;
+pub use github::GithubBackend;
+pub use gitlab::GitlabBackend;
+
+// localStorage keys for the selected forge and the non-GitHub forge connections
+const FORGE_STORAGE_KEY: &str = "oss-explorer.forge";
+const FORGE_SETTINGS_STORAGE_KEY: &str = "oss-explorer.forge-settings";
+
+/// The kinds of forge the app can search
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Forge {
+    #[default]
+    Github,
+    Gitlab,
+    Gitea,
+}
+
+impl Forge {
+    pub const ALL: &'static [Forge] = &[Forge::Github, Forge::Gitlab, Forge::Gitea];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Forge::Github => "GitHub",
+            Forge::Gitlab => "GitLab",
+            Forge::Gitea => "Gitea / Forgejo",
+        }
+    }
+
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Forge::Github => "github",
+            Forge::Gitlab => "gitlab",
+            Forge::Gitea => "gitea",
+        }
+    }
+
+    pub fn from_param(value: &str) -> Self {
+        match value {
+            "gitlab" => Forge::Gitlab,
+            "gitea" => Forge::Gitea,
+            _ => Forge::Github,
+        }
+    }
+}
+
+/// Where to reach a forge and the token to use there; an empty token searches anonymously
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForgeConnection {
+    pub url: String,
+    pub token: String,
+}
+
+impl ForgeConnection {
+    pub fn token(&self) -> Option<String> {
+        Some(self.token.clone()).filter(|t| !t.is_empty())
+    }
+}
+
+/// Connections for the forges other than GitHub, whose settings predate this and live under their own keys
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForgeSettings {
+    pub gitlab: ForgeConnection,
+    pub gitea: ForgeConnection,
+}
+
+impl Default for ForgeSettings {
+    fn default() -> Self {
+        ForgeSettings {
+            gitlab: ForgeConnection {
+                url: gitlab::GITLAB_URL.to_string(),
+                token: String::new(),
+            },
+            gitea: ForgeConnection {
+                url: gitea::CODEBERG_URL.to_string(),
+                token: String::new(),
+            },
+        }
+    }
+}
+
+/// Checks a repository against every filter, for forges whose search can't express some of them.
+/// Forges apply what they can server-side; re-checking those here is harmless.
+pub(crate) fn matches_filters(repo: &Repository, filters: &SearchFilters) -> bool {
+    let fork_ok = match filters.fork_filter {
+        ForkFilter::All => true,
+        ForkFilter::OriginalOnly => !repo.fork,
+        ForkFilter::ForksOnly => repo.fork,
+    };
+
+    let min_stars = filters
+        .min_stars
+        .trim_start_matches(">=")
+        .parse::<u32>()
+        .unwrap_or(0);
+
+    let language_ok = filters.language == "All"
+        || filters.language.is_empty()
+        || repo
+            .language
+            .as_deref()
+            .is_some_and(|l| l.eq_ignore_ascii_case(&filters.language));
+
+    let topics_ok = filters.topics.iter().all(|topic| {
+        repo.topics
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(topic))
+    });
+
+    // Dates compare correctly as strings since both sides start with YYYY-MM-DD
+    let in_range = |date: &str, from: &str, to: &str| {
+        let day = &date[..date.len().min(10)];
+        (from.is_empty() || day >= from) && (to.is_empty() || day <= to)
+    };
+    let created_ok = in_range(&repo.created_at, &filters.created_from, &filters.created_to);
+    let pushed_ok = in_range(&repo.updated_at, &filters.pushed_from, &filters.pushed_to);
+
+    fork_ok
+        && repo.stargazers_count >= min_stars
+        && language_ok
+        && topics_ok
+        && created_ok
+        && pushed_ok
+}
+
+/// The forge picked in the header, or None to search all of them at once;
+/// shared through context and remembered in localStorage
+#[derive(Clone, Copy)]
+pub struct SelectedForge(pub RwSignal<Option<Forge>>);
+
+impl SelectedForge {
+    pub fn load() -> Self {
+        SelectedForge(RwSignal::new(match storage::get_item(FORGE_STORAGE_KEY) {
+            Some(value) if value == "all" => None,
+            Some(value) => Some(Forge::from_param(&value)),
+            None => Some(Forge::default()),
+        }))
+    }
+
+    pub fn set(&self, forge: Option<Forge>) {
+        storage::set_item(FORGE_STORAGE_KEY, forge.map_or("all", |f| f.as_param()));
+        self.0.set(forge);
+    }
+
+    /// Whether searches currently go to this forge, alone or alongside the others
+    pub fn includes(&self, forge: Forge) -> bool {
+        self.0.get_untracked().is_none_or(|f| f == forge)
+    }
+}
+
+/// The GitLab and Gitea connections edited in Settings; their backends are rebuilt when it changes
+#[derive(Clone, Copy)]
+pub struct ForgeConfig(pub RwSignal<ForgeSettings>);
+
+impl ForgeConfig {
+    pub fn load() -> Self {
+        ForgeConfig(RwSignal::new(
+            storage::load_json(FORGE_SETTINGS_STORAGE_KEY).unwrap_or_default(),
+        ))
+    }
+
+    pub fn update(&self, f: impl FnOnce(&mut ForgeSettings)) {
+        self.0.update(|settings| {
+            f(settings);
+            storage::save_json(FORGE_SETTINGS_STORAGE_KEY, settings);
+        });
+    }
+}
+
+/// Orders repositories client-side, with the full name as a tiebreaker so the order is stable
+pub(crate) fn sort_repositories(items: &mut [Repository], sort_by: SortBy, order: SortOrder) {
+    items.sort_by(|a, b| {
+        let ordering = match sort_by {
+            SortBy::Stars => a.stargazers_count.cmp(&b.stargazers_count),
+            SortBy::Forks => a.forks_count.cmp(&b.forks_count),
+            SortBy::Issues => a.open_issues_count.cmp(&b.open_issues_count),
+            // ISO 8601 timestamps sort chronologically as strings
+            SortBy::Created => a.created_at.cmp(&b.created_at),
+            SortBy::Updated => a.updated_at.cmp(&b.updated_at),
+        };
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+        .then_with(|| a.full_name.cmp(&b.full_name))
+    });
+}
+
+/// Backend futures run on the browser's single thread, so they needn't be Send
+pub type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+pub trait SearchBackend: Send + Sync {
+    /// Human-readable name of the forge, used in messages
+    fn name(&self) -> &str;
+
+    fn forge(&self) -> Forge;
+
+    /// Root of the forge's web UI, e.g. https://github.com
+    fn web_url(&self) -> String;
+
+    /// Whether requests carry the user's credentials, which usually raises the quota
+    fn authenticated(&self) -> bool;
+
+    /// Most results a search can page through, if the forge caps it
+    fn max_results(&self) -> Option<u32> {
+        None
+    }
+
+    /// Largest page of results the forge returns; bigger requests come back with this many
+    fn max_per_page(&self) -> u32;
+
+    /// Whether results can be ordered this way; unsupported orders are disabled in the UI
+    fn supports_sort(&self, _sort_by: SortBy) -> bool {
+        true
+    }
+
+    /// Whether this fork filter is worth offering; unsupported ones are disabled in the UI
+    fn supports_fork_filter(&self, _fork_filter: ForkFilter) -> bool {
+        true
+    }
+
+    /// Runs a repository search for one page of results
+    fn search<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>>;
+
+    /// Like `search`, but skips any cached response so the forge is asked again
+    fn refresh<'a>(
+        &'a self,
+        filters: &'a SearchFilters,
+    ) -> BackendFuture<'a, Result<SearchResult, SearchError>> {
+        self.search(filters)
+    }
+
+    /// Fetches a single repository by its `owner/name` path
+    fn fetch_repository<'a>(
+        &'a self,
+        full_name: &'a str,
+    ) -> BackendFuture<'a, Result<Repository, SearchError>>;
+
+    /// Current search quota, without spending any of it; None if the backend doesn't limit requests
+    fn rate_limit(&self) -> BackendFuture<'_, Result<Option<RateLimitInfo>, SearchError>>;
+}
+
+/// The backend every page searches through, shared through context.
+/// It is a signal because the backend is rebuilt when its settings (e.g. the token) change.
+#[derive(Clone, Copy)]
+pub struct ActiveBackend(pub Signal<Arc<dyn SearchBackend>>);
+
+impl ActiveBackend {
+    pub fn get_untracked(&self) -> Arc<dyn SearchBackend> {
+        self.0.get_untracked()
+    }
+}
@@ -0,0 +1,190 @@
+// Search response cache: in memory for the session, optionally persisted to IndexedDB
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::storage;
+
+// Responses younger than this are served without asking the API at all;
+// older ones are revalidated with their ETag
+const CACHE_TTL_MS: f64 = 5.0 * 60.0 * 1000.0;
+
+// Oldest entries are dropped beyond this many
+const MAX_MEMORY_ENTRIES: usize = 200;
+
+// localStorage flag for keeping responses in IndexedDB between visits
+const PERSISTENT_CACHE_STORAGE_KEY: &str = "oss-explorer.persistent-cache";
+
+const DB_NAME: &str = "oss-explorer-cache";
+const STORE_NAME: &str = "responses";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: Option<String>,
+    pub body: String,
+    /// When the response was fetched or last revalidated, in milliseconds since the epoch
+    pub fetched_at: f64,
+}
+
+impl CachedResponse {
+    pub fn is_fresh(&self) -> bool {
+        js_sys::Date::now() - self.fetched_at < CACHE_TTL_MS
+    }
+}
+
+thread_local! {
+    static MEMORY: RefCell<HashMap<String, CachedResponse>> = RefCell::new(HashMap::new());
+}
+
+pub fn persistent_enabled() -> bool {
+    storage::get_item(PERSISTENT_CACHE_STORAGE_KEY).is_some_and(|v| v == "1")
+}
+
+pub fn set_persistent_enabled(enabled: bool) {
+    if enabled {
+        storage::set_item(PERSISTENT_CACHE_STORAGE_KEY, "1");
+    } else {
+        storage::remove_item(PERSISTENT_CACHE_STORAGE_KEY);
+        leptos::task::spawn_local(async {
+            let _ = clear_persistent().await;
+        });
+    }
+}
+
+/// The cache key of a search URL as requested with `token`. Results fetched with a token can
+/// include private repositories, so each token gets its own entries; a hash of the token keeps
+/// it out of IndexedDB.
+pub fn key(url: &str, token: Option<&str>) -> String {
+    match token {
+        Some(token) => {
+            // FNV-1a: only needs to tell tokens apart, not resist anyone
+            let hash = token.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+            format!("{}#token={:016x}", url, hash)
+        }
+        None => url.to_string(),
+    }
+}
+
+/// Looks a cache key up in memory, then in IndexedDB when persistence is on
+pub async fn lookup(url: &str) -> Option<CachedResponse> {
+    if let Some(entry) = MEMORY.with(|m| m.borrow().get(url).cloned()) {
+        return Some(entry);
+    }
+    if !persistent_enabled() {
+        return None;
+    }
+
+    let entry: CachedResponse = persistent_get(url)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())?;
+    remember(url, entry.clone());
+    Some(entry)
+}
+
+/// Stores (or, after a 304, re-dates) a response
+pub fn store(url: &str, etag: Option<String>, body: String) {
+    let entry = CachedResponse {
+        etag,
+        body,
+        fetched_at: js_sys::Date::now(),
+    };
+
+    if persistent_enabled() {
+        if let Ok(raw) = serde_json::to_string(&entry) {
+            let url = url.to_string();
+            leptos::task::spawn_local(async move {
+                let _ = persistent_put(&url, &raw).await;
+            });
+        }
+    }
+    remember(url, entry);
+}
+
+fn remember(url: &str, entry: CachedResponse) {
+    MEMORY.with(|m| {
+        let mut memory = m.borrow_mut();
+        if memory.len() >= MAX_MEMORY_ENTRIES && !memory.contains_key(url) {
+            let oldest = memory
+                .iter()
+                .min_by(|a, b| a.1.fetched_at.total_cmp(&b.1.fetched_at))
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                memory.remove(&oldest);
+            }
+        }
+        memory.insert(url.to_string(), entry);
+    });
+}
+
+/// Resolves when an IndexedDB request succeeds, with its result
+fn request_future(request: &web_sys::IdbRequest) -> JsFuture {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let succeeded = request.clone();
+        let on_success = Closure::once_into_js(move |_: web_sys::Event| {
+            let result = succeeded.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::NULL, &result);
+        });
+        let on_error = Closure::once_into_js(move |_: web_sys::Event| {
+            let _ = reject.call0(&JsValue::NULL);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise)
+}
+
+async fn open_db() -> Result<web_sys::IdbDatabase, JsValue> {
+    let factory = web_sys::window()
+        .and_then(|w| w.indexed_db().ok().flatten())
+        .ok_or_else(|| JsValue::from_str("IndexedDB is unavailable"))?;
+    let request = factory.open_with_u32(DB_NAME, 1)?;
+
+    let upgrading = request.clone();
+    let on_upgrade = Closure::once_into_js(move |_: web_sys::Event| {
+        if let Ok(db) = upgrading
+            .result()
+            .and_then(|r| r.dyn_into::<web_sys::IdbDatabase>())
+        {
+            let _ = db.create_object_store(STORE_NAME);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+    request_future(&request).await?.dyn_into()
+}
+
+async fn persistent_get(url: &str) -> Result<Option<String>, JsValue> {
+    let db = open_db().await?;
+    let request = db
+        .transaction_with_str(STORE_NAME)?
+        .object_store(STORE_NAME)?
+        .get(&JsValue::from_str(url))?;
+    Ok(request_future(&request).await?.as_string())
+}
+
+async fn persistent_put(url: &str, raw: &str) -> Result<(), JsValue> {
+    let db = open_db().await?;
+    let request = db
+        .transaction_with_str_and_mode(STORE_NAME, web_sys::IdbTransactionMode::Readwrite)?
+        .object_store(STORE_NAME)?
+        .put_with_key(&JsValue::from_str(raw), &JsValue::from_str(url))?;
+    request_future(&request).await.map(|_| ())
+}
+
+async fn clear_persistent() -> Result<(), JsValue> {
+    let db = open_db().await?;
+    let request = db
+        .transaction_with_str_and_mode(STORE_NAME, web_sys::IdbTransactionMode::Readwrite)?
+        .object_store(STORE_NAME)?
+        .clear()?;
+    request_future(&request).await.map(|_| ())
+}
@@ -0,0 +1,558 @@
+// Command-line mode of the native binary: `oss-explorer search ...` for shell pipelines,
+// `oss-explorer tui ...` for browsing interactively, `oss-explorer serve` for a team proxy and
+// `oss-explorer generate` for the curated snapshot pages
+
+use std::io::Write;
+use std::process::ExitCode;
+
+use crate::backend::github::{default_api_url, normalize_api_url, MAX_PER_PAGE, MAX_RESULTS};
+use crate::error::SearchError;
+use crate::{
+    build_search_query, calculate_total_pages, export, format_date, format_number, generate,
+    native, normalize_topic, parse_date_param, proxy, tui, ArchivedFilter, ForkFilter, Repository,
+    SearchFilters, SearchResult, SortBy, SortOrder,
+};
+
+const USAGE: &str = "\
+Usage: oss-explorer search [OPTIONS] [QUERY...]
+       oss-explorer tui [OPTIONS] [QUERY...]
+       oss-explorer serve [SERVE OPTIONS]
+       oss-explorer generate [GENERATE OPTIONS]
+
+`search` searches GitHub repositories and prints one page of results; `tui` opens
+the same search in an interactive terminal explorer (ignoring --format). `serve`
+hosts the built web app and proxies its searches through one shared token and cache.
+`generate` runs the curated search presets in a config file and writes their results
+into the built app as static pages and JSON snapshots.
+
+Options:
+      --lang <LANGUAGE>        Only repositories in this language
+      --min-stars <N|RANGE>    At least N stars, or a range such as 10..100
+      --topic <TOPIC>          Require a topic (repeatable; all must match)
+      --created-from <DATE>    Created on or after YYYY-MM-DD
+      --created-to <DATE>      Created on or before YYYY-MM-DD
+      --pushed-from <DATE>     Pushed to on or after YYYY-MM-DD
+      --pushed-to <DATE>       Pushed to on or before YYYY-MM-DD
+      --forks <FILTER>         all, original or forks [default: all]
+      --archived <FILTER>      active, archived or all [default: active]
+      --sort <FIELD>           stars, forks, issues, created or updated [default: stars]
+      --order <ORDER>          desc or asc [default: desc]
+      --page <N>               Page of results [default: 1]
+      --per-page <N>           Results per page, up to 100 [default: 30]
+      --format <FORMAT>        table, json or csv [default: table]
+      --token <TOKEN>          GitHub token [env: GITHUB_TOKEN]
+      --api-url <URL>          API base URL, e.g. for GitHub Enterprise [env: OSS_EXPLORER_API_URL]
+      --fixture <FILE>         Read a recorded search response instead of calling the API
+  -h, --help                   Print this help
+
+Serve options:
+      --listen <ADDR>          Address to listen on [default: 127.0.0.1:8787]
+      --dist <DIR>             Built web app to serve [default: dist]
+      --token <TOKEN>          GitHub token every proxied search uses [env: GITHUB_TOKEN]
+      --api-url <URL>          API base URL to proxy [env: OSS_EXPLORER_API_URL]
+
+Generate options:
+      --config <FILE>          Search presets to generate [default: top-repos.json]
+      --dist <DIR>             Built web app to write top/ into [default: dist]
+      --token <TOKEN>          GitHub token [env: GITHUB_TOKEN]
+      --api-url <URL>          API base URL [env: OSS_EXPLORER_API_URL]
+";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug)]
+pub struct SearchArgs {
+    pub filters: SearchFilters,
+    pub format: OutputFormat,
+    pub api_url: String,
+    pub token: Option<String>,
+    /// A saved search response to serve instead of querying the API, for working offline
+    pub fixture: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ServeArgs {
+    pub listen: String,
+    pub dist: String,
+    pub api_url: String,
+    pub token: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct GenerateArgs {
+    pub config: String,
+    pub dist: String,
+    pub api_url: String,
+    pub token: Option<String>,
+}
+
+/// Runs the command line given the arguments after the program name
+pub fn run(args: &[String]) -> ExitCode {
+    match args.first().map(String::as_str) {
+        Some("search" | "tui" | "serve" | "generate")
+            if args.iter().any(|a| a == "-h" || a == "--help") =>
+        {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Some("search") => match parse_search_args(&args[1..]) {
+            Ok(search) => run_search(search),
+            Err(e) => usage_error(&e),
+        },
+        Some("tui") => match parse_search_args(&args[1..]) {
+            Ok(search) => tui::run(search),
+            Err(e) => usage_error(&e),
+        },
+        Some("serve") => match parse_serve_args(&args[1..]) {
+            Ok(serve) => proxy::run(serve),
+            Err(e) => usage_error(&e),
+        },
+        Some("generate") => match parse_generate_args(&args[1..]) {
+            Ok(generate) => generate::run(generate),
+            Err(e) => usage_error(&e),
+        },
+        Some("-h" | "--help" | "help") => {
+            print!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Some(command) => usage_error(&format!("unknown command '{}'", command)),
+        None => usage_error("no command given"),
+    }
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    ExitCode::from(2)
+}
+
+/// Parses a value against the param names an enum round-trips through, rejecting anything else
+fn parse_choice<T>(
+    flag: &str,
+    value: &str,
+    from_param: fn(&str) -> T,
+    as_param: fn(&T) -> &'static str,
+) -> Result<T, String> {
+    let parsed = from_param(value);
+    if as_param(&parsed) == value {
+        Ok(parsed)
+    } else {
+        Err(format!("invalid value '{}' for {}", value, flag))
+    }
+}
+
+fn parse_date(flag: &str, value: &str) -> Result<String, String> {
+    parse_date_param(value).ok_or_else(|| format!("{} expects a YYYY-MM-DD date", flag))
+}
+
+fn env_api_url() -> String {
+    std::env::var("OSS_EXPLORER_API_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| normalize_api_url(&url))
+        .unwrap_or_else(default_api_url)
+}
+
+fn env_token() -> Option<String> {
+    std::env::var("GITHUB_TOKEN")
+        .ok()
+        .filter(|t| !t.trim().is_empty())
+}
+
+/// Splits an option into its flag and value; both `--flag value` and `--flag=value` are accepted
+fn flag_value<'a>(
+    arg: &'a str,
+    rest: &mut impl Iterator<Item = &'a String>,
+) -> Result<(&'a str, String), String> {
+    match arg.split_once('=') {
+        Some((flag, value)) => Ok((flag, value.to_string())),
+        None => rest
+            .next()
+            .map(|value| (arg, value.clone()))
+            .ok_or_else(|| format!("{} needs a value", arg)),
+    }
+}
+
+pub fn parse_search_args(args: &[String]) -> Result<SearchArgs, String> {
+    let mut filters = SearchFilters::default();
+    let mut format = OutputFormat::Table;
+    let mut api_url = env_api_url();
+    let mut token = env_token();
+    let mut fixture = None;
+    let mut words = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            words.push(arg.clone());
+            continue;
+        }
+        let (flag, value) = flag_value(arg, &mut args)?;
+
+        match flag {
+            "--lang" => filters.language = value,
+            "--min-stars" => {
+                filters.min_stars = if value.chars().all(|c| c.is_ascii_digit()) {
+                    format!(">={}", value)
+                } else {
+                    value
+                }
+            }
+            "--topic" => {
+                let topic = normalize_topic(&value);
+                if !topic.is_empty() && !filters.topics.contains(&topic) {
+                    filters.topics.push(topic);
+                }
+            }
+            "--created-from" => filters.created_from = parse_date(flag, &value)?,
+            "--created-to" => filters.created_to = parse_date(flag, &value)?,
+            "--pushed-from" => filters.pushed_from = parse_date(flag, &value)?,
+            "--pushed-to" => filters.pushed_to = parse_date(flag, &value)?,
+            "--forks" => {
+                filters.fork_filter =
+                    parse_choice(flag, &value, ForkFilter::from_param, ForkFilter::as_param)?
+            }
+            "--archived" => {
+                filters.archived_filter = parse_choice(
+                    flag,
+                    &value,
+                    ArchivedFilter::from_param,
+                    ArchivedFilter::as_param,
+                )?
+            }
+            "--sort" => {
+                filters.sort_by = parse_choice(flag, &value, SortBy::from_param, SortBy::as_param)?
+            }
+            "--order" => {
+                filters.sort_order =
+                    parse_choice(flag, &value, SortOrder::from_param, SortOrder::as_str)?
+            }
+            "--page" => {
+                filters.page = value
+                    .parse()
+                    .ok()
+                    .filter(|&page| page >= 1)
+                    .ok_or_else(|| format!("{} expects a positive number", flag))?
+            }
+            "--per-page" => {
+                filters.per_page = value
+                    .parse()
+                    .ok()
+                    .filter(|n| (1..=MAX_PER_PAGE).contains(n))
+                    .ok_or_else(|| {
+                        format!("{} expects a number from 1 to {}", flag, MAX_PER_PAGE)
+                    })?
+            }
+            "--format" => {
+                format = match value.as_str() {
+                    "table" => OutputFormat::Table,
+                    "json" => OutputFormat::Json,
+                    "csv" => OutputFormat::Csv,
+                    _ => return Err(format!("invalid value '{}' for {}", value, flag)),
+                }
+            }
+            "--token" => token = Some(value).filter(|t| !t.trim().is_empty()),
+            "--api-url" => api_url = normalize_api_url(&value),
+            "--fixture" => fixture = Some(value),
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+    }
+
+    filters.query = words.join(" ");
+    Ok(SearchArgs {
+        filters,
+        format,
+        api_url,
+        token,
+        fixture,
+    })
+}
+
+pub fn parse_serve_args(args: &[String]) -> Result<ServeArgs, String> {
+    let mut serve = ServeArgs {
+        listen: "127.0.0.1:8787".to_string(),
+        dist: "dist".to_string(),
+        api_url: env_api_url(),
+        token: env_token(),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            return Err(format!("unexpected argument '{}'", arg));
+        }
+        let (flag, value) = flag_value(arg, &mut args)?;
+        match flag {
+            "--listen" => serve.listen = value,
+            "--dist" => serve.dist = value,
+            "--token" => serve.token = Some(value).filter(|t| !t.trim().is_empty()),
+            "--api-url" => serve.api_url = normalize_api_url(&value),
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+    }
+    Ok(serve)
+}
+
+pub fn parse_generate_args(args: &[String]) -> Result<GenerateArgs, String> {
+    let mut generate = GenerateArgs {
+        config: "top-repos.json".to_string(),
+        dist: "dist".to_string(),
+        api_url: env_api_url(),
+        token: env_token(),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            return Err(format!("unexpected argument '{}'", arg));
+        }
+        let (flag, value) = flag_value(arg, &mut args)?;
+        match flag {
+            "--config" => generate.config = value,
+            "--dist" => generate.dist = value,
+            "--token" => generate.token = Some(value).filter(|t| !t.trim().is_empty()),
+            "--api-url" => generate.api_url = normalize_api_url(&value),
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+    }
+    Ok(generate)
+}
+
+/// Runs the search the arguments describe, from the fixture file if one was given
+pub(crate) fn fetch(search: &SearchArgs) -> Result<SearchResult, SearchError> {
+    match &search.fixture {
+        Some(path) => native::search_fixture(path),
+        None => native::search(&search.api_url, search.token.as_deref(), &search.filters),
+    }
+}
+
+fn run_search(search: SearchArgs) -> ExitCode {
+    let result = match fetch(&search) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            eprintln!("query: {}", build_search_query(&search.filters));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // The summary goes to stderr so stdout stays clean for pipelines
+    let total = result.response.total_count;
+    let pages = calculate_total_pages(total, search.filters.per_page, Some(MAX_RESULTS));
+    eprintln!(
+        "{} repositories found (page {} of {})",
+        format_number(total),
+        search.filters.page,
+        pages.max(1)
+    );
+
+    let items = &result.response.items;
+    let output = match search.format {
+        OutputFormat::Table => render_table(items),
+        OutputFormat::Json => export::to_json(items) + "\n",
+        OutputFormat::Csv => export::to_csv(items),
+    };
+    // A closed pipe (e.g. `| head`) isn't worth reporting
+    let _ = std::io::stdout().lock().write_all(output.as_bytes());
+    ExitCode::SUCCESS
+}
+
+/// Column headings of the results table, the same columns as the web app's
+pub const TABLE_COLUMNS: [&str; 6] = [
+    "Repository",
+    "Language",
+    "Stars",
+    "Forks",
+    "Issues",
+    "Created",
+];
+
+/// One results table row, in TABLE_COLUMNS order
+pub fn table_row(repo: &Repository) -> [String; 6] {
+    [
+        repo.full_name.clone(),
+        repo.language
+            .clone()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        format_number(repo.stargazers_count),
+        format_number(repo.forks_count),
+        format_number(repo.open_issues_count),
+        format_date(&repo.created_at),
+    ]
+}
+
+/// Aligns the results into plain-text columns, numbers right-aligned
+pub fn render_table(repos: &[Repository]) -> String {
+    let rows: Vec<[String; 6]> = repos.iter().map(table_row).collect();
+    let mut widths = TABLE_COLUMNS.map(|heading| heading.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render_row = |cells: [&str; 6]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                if (2..=4).contains(&i) {
+                    format!("{:>width$}", cell, width = width)
+                } else {
+                    format!("{:<width$}", cell, width = width)
+                }
+            })
+            .collect();
+        line.join("  ").trim_end().to_string() + "\n"
+    };
+
+    let mut out = render_row(
+        TABLE_COLUMNS
+            .map(str::to_uppercase)
+            .each_ref()
+            .map(String::as_str),
+    );
+    for row in &rows {
+        out.push_str(&render_row(row.each_ref().map(String::as_str)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<SearchArgs, String> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        parse_search_args(&args)
+    }
+
+    #[test]
+    fn render_table_aligns_columns() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/search-rust.json");
+        let result = native::search_fixture(path).unwrap();
+        assert_eq!(
+            render_table(&result.response.items),
+            "\
+REPOSITORY          LANGUAGE   STARS  FORKS  ISSUES  CREATED
+rust-lang/rust      Rust      101.2K  13.1K    9.9K  2010-06-16
+denoland/deno       Rust       98.8K   5.4K    2.1K  2018-05-15
+BurntSushi/ripgrep  Rust       52.0K   2.1K      95  2016-03-11
+"
+        );
+    }
+
+    #[test]
+    fn render_table_without_results_is_just_the_headings() {
+        assert_eq!(
+            render_table(&[]),
+            "REPOSITORY  LANGUAGE  STARS  FORKS  ISSUES  CREATED\n"
+        );
+    }
+
+    #[test]
+    fn parses_search_options() {
+        let search = parse(&[
+            "async",
+            "--lang",
+            "Rust",
+            "runtime",
+            "--min-stars=500",
+            "--topic",
+            "Web Framework",
+            "--created-from",
+            "2020-01-01",
+            "--forks",
+            "original",
+            "--archived=all",
+            "--sort",
+            "updated",
+            "--order",
+            "asc",
+            "--page",
+            "2",
+            "--per-page",
+            "100",
+            "--format",
+            "csv",
+            "--fixture",
+            "saved.json",
+        ])
+        .unwrap();
+        let filters = &search.filters;
+        assert_eq!(filters.query, "async runtime");
+        assert_eq!(filters.language, "Rust");
+        assert_eq!(filters.min_stars, ">=500");
+        assert_eq!(filters.topics, ["web-framework"]);
+        assert_eq!(filters.created_from, "2020-01-01");
+        assert_eq!(filters.fork_filter, ForkFilter::OriginalOnly);
+        assert_eq!(filters.archived_filter, ArchivedFilter::All);
+        assert_eq!(filters.sort_by, SortBy::Updated);
+        assert_eq!(filters.sort_order, SortOrder::Asc);
+        assert_eq!(filters.page, 2);
+        assert_eq!(filters.per_page, MAX_PER_PAGE);
+        assert_eq!(search.format, OutputFormat::Csv);
+        assert_eq!(search.fixture.as_deref(), Some("saved.json"));
+    }
+
+    #[test]
+    fn star_ranges_pass_through() {
+        let search = parse(&["--min-stars", "10..100"]).unwrap();
+        assert_eq!(search.filters.min_stars, "10..100");
+    }
+
+    #[test]
+    fn defaults_match_the_web_app() {
+        let search = parse(&[]).unwrap();
+        assert_eq!(search.filters, SearchFilters::default());
+        assert_eq!(search.format, OutputFormat::Table);
+    }
+
+    #[test]
+    fn rejects_out_of_range_page_sizes() {
+        for per_page in ["0", "101", "many"] {
+            assert_eq!(
+                parse(&["--per-page", per_page]).unwrap_err(),
+                "--per-page expects a number from 1 to 100"
+            );
+        }
+        assert_eq!(
+            parse(&["--page=0"]).unwrap_err(),
+            "--page expects a positive number"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_choices() {
+        assert_eq!(
+            parse(&["--sort=bogus"]).unwrap_err(),
+            "invalid value 'bogus' for --sort"
+        );
+        assert_eq!(
+            parse(&["--order", "up"]).unwrap_err(),
+            "invalid value 'up' for --order"
+        );
+        assert_eq!(
+            parse(&["--format", "xml"]).unwrap_err(),
+            "invalid value 'xml' for --format"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_arguments() {
+        assert_eq!(
+            parse(&["--created-to", "yesterday"]).unwrap_err(),
+            "--created-to expects a YYYY-MM-DD date"
+        );
+        assert_eq!(parse(&["--lang"]).unwrap_err(), "--lang needs a value");
+        assert_eq!(
+            parse(&["--colour", "red"]).unwrap_err(),
+            "unknown option '--colour'"
+        );
+    }
+}
@@ -0,0 +1,531 @@
+// Repository detail page: the repository from its forge, plus README, languages, contributors,
+// latest release and commit activity from GitHub-only endpoints
+
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_params_map, use_query_map};
+use serde::Deserialize;
+
+use crate::backend::github::{github_request, JSON_ACCEPT};
+use crate::backend::Forge;
+use crate::error::{error_from_response, SearchError};
+use crate::retry::{with_retry, RetryPolicy};
+use crate::{
+    format_date, format_number, get_safe_avatar_url, ForgeBackends, GithubApiUrl, GithubToken,
+    Repository, DEFAULT_AVATAR,
+};
+
+#[derive(Clone, Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    name: Option<String>,
+    html_url: String,
+    published_at: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct WeeklyCommits {
+    total: u32,
+    week: u64,
+}
+
+// Number of recent weeks shown in the commit activity chart
+const ACTIVITY_WEEKS: usize = 12;
+
+// Colors cycled through for the language breakdown bar
+const LANGUAGE_COLORS: &[&str] = &[
+    "#58a6ff", "#a371f7", "#3fb950", "#d29922", "#f85149", "#db61a2", "#79c0ff", "#8b949e",
+];
+
+/// Fetches the README rendered to HTML by GitHub; Ok(None) means the repository has no README
+async fn fetch_readme_html(
+    api_url: &str,
+    full_name: &str,
+    token: Option<&str>,
+) -> Result<Option<String>, SearchError> {
+    let url = format!("{}/repos/{}/readme", api_url, full_name);
+    let response = github_request(&url, "application/vnd.github.html+json", token)
+        .send()
+        .await?;
+
+    if response.status() == 404 {
+        return Ok(None);
+    }
+    if !response.ok() {
+        return Err(error_from_response(response).await);
+    }
+
+    Ok(Some(response.text().await?))
+}
+
+/// Returns languages sorted by byte count, largest first
+async fn fetch_languages(
+    api_url: &str,
+    full_name: &str,
+    token: Option<&str>,
+) -> Result<Vec<(String, u64)>, SearchError> {
+    let url = format!("{}/repos/{}/languages", api_url, full_name);
+    let response = github_request(&url, JSON_ACCEPT, token).send().await?;
+
+    if !response.ok() {
+        return Err(error_from_response(response).await);
+    }
+
+    let languages = response
+        .json::<std::collections::HashMap<String, u64>>()
+        .await?;
+
+    let mut languages: Vec<_> = languages.into_iter().collect();
+    languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(languages)
+}
+
+/// Counts contributors by requesting one per page and reading the last page number from the Link header
+async fn fetch_contributor_count(
+    api_url: &str,
+    full_name: &str,
+    token: Option<&str>,
+) -> Result<u32, SearchError> {
+    let url = format!(
+        "{}/repos/{}/contributors?per_page=1&anon=true",
+        api_url, full_name
+    );
+    let response = github_request(&url, JSON_ACCEPT, token).send().await?;
+
+    // GitHub answers 204 for empty repositories
+    if response.status() == 204 {
+        return Ok(0);
+    }
+    if !response.ok() {
+        return Err(error_from_response(response).await);
+    }
+
+    if let Some(last_page) = response
+        .headers()
+        .get("link")
+        .and_then(|link| parse_last_page(&link))
+    {
+        return Ok(last_page);
+    }
+
+    let contributors = response.json::<Vec<serde_json::Value>>().await?;
+    Ok(contributors.len() as u32)
+}
+
+/// Extracts the page number of the rel="last" entry of a GitHub Link header
+fn parse_last_page(link: &str) -> Option<u32> {
+    link.split(',')
+        .find(|part| part.contains("rel=\"last\""))
+        .and_then(|part| {
+            let url = part.split(';').next()?.trim().trim_matches(['<', '>']);
+            url.split(['?', '&'])
+                .find_map(|param| param.strip_prefix("page="))
+                .and_then(|page| page.parse().ok())
+        })
+}
+
+/// Ok(None) means the repository has not published a release
+async fn fetch_latest_release(
+    api_url: &str,
+    full_name: &str,
+    token: Option<&str>,
+) -> Result<Option<Release>, SearchError> {
+    let url = format!("{}/repos/{}/releases/latest", api_url, full_name);
+    let response = github_request(&url, JSON_ACCEPT, token).send().await?;
+
+    if response.status() == 404 {
+        return Ok(None);
+    }
+    if !response.ok() {
+        return Err(error_from_response(response).await);
+    }
+
+    Ok(Some(response.json::<Release>().await?))
+}
+
+/// Weekly commit totals for the last year; Ok(None) while GitHub is still computing the statistics
+async fn fetch_commit_activity(
+    api_url: &str,
+    full_name: &str,
+    token: Option<&str>,
+) -> Result<Option<Vec<WeeklyCommits>>, SearchError> {
+    let url = format!("{}/repos/{}/stats/commit_activity", api_url, full_name);
+    let response = github_request(&url, JSON_ACCEPT, token).send().await?;
+
+    if response.status() == 202 {
+        return Ok(None);
+    }
+    if !response.ok() {
+        return Err(error_from_response(response).await);
+    }
+
+    Ok(Some(response.json::<Vec<WeeklyCommits>>().await?))
+}
+
+/// Loads one section, retrying transient failures while `wanted` holds; the error is the
+/// message the section shows
+async fn load_section<T, F, Fut>(fetch: F, wanted: impl Fn() -> bool) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, SearchError>>,
+{
+    with_retry(RetryPolicy::default(), fetch, |_| {}, wanted)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn format_week(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|d| d.format("%b %d").to_string())
+        .unwrap_or_default()
+}
+
+#[component]
+pub fn RepoDetail() -> impl IntoView {
+    let params = use_params_map();
+    let navigate = StoredValue::new(use_navigate());
+    let github_token = expect_context::<GithubToken>();
+    let api_url = expect_context::<GithubApiUrl>();
+    let forge_backends = expect_context::<ForgeBackends>();
+    let query = use_query_map();
+
+    // Links name the repository's forge unless it's GitHub, so plain /repo/owner/name links
+    // (shared, watched or from the snapshot pages) stay on GitHub whatever forge is being searched
+    let page_forge = move || {
+        query.with(|q| {
+            q.get_str("forge")
+                .map_or(Forge::default(), Forge::from_param)
+        })
+    };
+
+    // A wildcard rather than owner/name, since GitLab projects can sit in nested groups
+    let full_name = move || {
+        params.with(|p| {
+            p.get_str("full_name")
+                .unwrap_or_default()
+                .trim_matches('/')
+                .to_string()
+        })
+    };
+
+    // Each section is None while loading, then holds the fetch result
+    let (repository, set_repository) = signal(Option::<Result<Repository, String>>::None);
+    let (readme, set_readme) = signal(Option::<Result<Option<String>, String>>::None);
+    let (languages, set_languages) = signal(Option::<Result<Vec<(String, u64)>, String>>::None);
+    let (contributors, set_contributors) = signal(Option::<Result<u32, String>>::None);
+    let (release, set_release) = signal(Option::<Result<Option<Release>, String>>::None);
+    let (activity, set_activity) =
+        signal(Option::<Result<Option<Vec<WeeklyCommits>>, String>>::None);
+    // Bumped for every repository shown, so sections still loading for the previous one
+    // stop retrying and don't overwrite this one's
+    let load_generation = StoredValue::new(0u64);
+
+    Effect::new(move |_| {
+        load_generation.update_value(|g| *g += 1);
+        let generation = load_generation.get_value();
+        // False once another repository is shown, or the page is gone
+        let is_current = move || load_generation.try_get_value() == Some(generation);
+
+        let name = full_name();
+        let token = github_token.get_untracked();
+        let api_url = api_url.0.get_untracked();
+        let backend = forge_backends.get_untracked(page_forge());
+        let forge = backend.forge();
+
+        set_repository.set(None);
+        set_readme.set(None);
+        set_languages.set(None);
+        set_contributors.set(None);
+        set_release.set(None);
+        set_activity.set(None);
+
+        // Sections load independently so a slow endpoint doesn't hold up the rest of the page
+        {
+            let name = name.clone();
+            leptos::task::spawn_local(async move {
+                let result = backend.fetch_repository(&name).await.map_err(|e| match e {
+                    SearchError::Http(404) => format!("Repository {} was not found.", name),
+                    e => e.to_string(),
+                });
+                if is_current() {
+                    set_repository.set(Some(result));
+                }
+            });
+        }
+
+        // The remaining sections come from GitHub-only endpoints
+        if forge != Forge::Github {
+            let unavailable = format!("Not available for {} projects.", forge.label());
+            set_readme.set(Some(Err(unavailable.clone())));
+            set_languages.set(Some(Err(unavailable.clone())));
+            set_contributors.set(Some(Err(unavailable.clone())));
+            set_release.set(Some(Err(unavailable.clone())));
+            set_activity.set(Some(Err(unavailable)));
+            return;
+        }
+        {
+            let (name, token, api_url) = (name.clone(), token.clone(), api_url.clone());
+            leptos::task::spawn_local(async move {
+                let result = load_section(
+                    || fetch_readme_html(&api_url, &name, token.as_deref()),
+                    is_current,
+                )
+                .await;
+                if is_current() {
+                    set_readme.set(Some(result));
+                }
+            });
+        }
+        {
+            let (name, token, api_url) = (name.clone(), token.clone(), api_url.clone());
+            leptos::task::spawn_local(async move {
+                let result = load_section(
+                    || fetch_languages(&api_url, &name, token.as_deref()),
+                    is_current,
+                )
+                .await;
+                if is_current() {
+                    set_languages.set(Some(result));
+                }
+            });
+        }
+        {
+            let (name, token, api_url) = (name.clone(), token.clone(), api_url.clone());
+            leptos::task::spawn_local(async move {
+                let result = load_section(
+                    || fetch_contributor_count(&api_url, &name, token.as_deref()),
+                    is_current,
+                )
+                .await;
+                if is_current() {
+                    set_contributors.set(Some(result));
+                }
+            });
+        }
+        {
+            let (name, token, api_url) = (name.clone(), token.clone(), api_url.clone());
+            leptos::task::spawn_local(async move {
+                let result = load_section(
+                    || fetch_latest_release(&api_url, &name, token.as_deref()),
+                    is_current,
+                )
+                .await;
+                if is_current() {
+                    set_release.set(Some(result));
+                }
+            });
+        }
+        leptos::task::spawn_local(async move {
+            let result = load_section(
+                || fetch_commit_activity(&api_url, &name, token.as_deref()),
+                is_current,
+            )
+            .await;
+            if is_current() {
+                set_activity.set(Some(result));
+            }
+        });
+    });
+
+    let go_back = move |_| {
+        // Return to the previous search (and its URL-encoded filters) when we came from one
+        let history = web_sys::window().and_then(|w| w.history().ok());
+        match history {
+            Some(history) if history.length().unwrap_or(0) > 1 => {
+                let _ = history.back();
+            }
+            _ => navigate.with_value(|navigate| navigate("/", Default::default())),
+        }
+    };
+
+    view! {
+        <div class="repo-detail">
+            <button class="toggle-btn back-btn" on:click=go_back>"← Back to search"</button>
+
+            {move || match repository.get() {
+                None => view! { <div class="loading">"Loading repository..."</div> }.into_any(),
+                Some(Err(e)) => view! {
+                    <div class="error"><strong>"Error: "</strong>{e}</div>
+                }.into_any(),
+                Some(Ok(repo)) => {
+                    let avatar = get_safe_avatar_url(&repo.owner.avatar_url);
+                    let fallback_avatar = DEFAULT_AVATAR.to_string();
+                    view! {
+                        <div class="detail-header">
+                            <img
+                                src=avatar
+                                alt="avatar"
+                                class="avatar detail-avatar"
+                                on:error=move |ev| {
+                                    // Replace with default avatar on load error
+                                    if let Some(target) = ev.target() {
+                                        use wasm_bindgen::JsCast;
+                                        if let Ok(img) = target.dyn_into::<web_sys::HtmlImageElement>() {
+                                            img.set_src(&fallback_avatar);
+                                        }
+                                    }
+                                }
+                            />
+                            <div class="repo-details">
+                                <div class="repo-name-row">
+                                    <h2 class="detail-title">{repo.full_name.clone()}</h2>
+                                    {repo.fork.then(|| view! { <span class="badge fork-badge">"Fork"</span> })}
+                                    {repo.archived.then(|| view! { <span class="badge archived-badge">"Archived"</span> })}
+                                </div>
+                                <p class="repo-description">{repo.description.clone().unwrap_or_default()}</p>
+                                {repo.topics.clone().filter(|t| !t.is_empty()).map(|topics| view! {
+                                    <div class="topic-chips">
+                                        {topics.into_iter().map(|topic| view! {
+                                            <A href=format!("/?topics={}", topic) attr:class="topic-chip">{topic}</A>
+                                        }).collect::<Vec<_>>()}
+                                    </div>
+                                })}
+                                <a href=repo.html_url.clone() target="_blank" class="external-link">{format!("View on {} ↗", repo.forge.unwrap_or_else(page_forge).label())}</a>
+                            </div>
+                        </div>
+
+                        <div class="detail-stats">
+                            <div class="detail-stat">
+                                <span class="stat">{format_number(repo.stargazers_count)}</span>
+                                <span class="detail-stat-label">"Stars"</span>
+                            </div>
+                            <div class="detail-stat">
+                                <span class="stat">{format_number(repo.forks_count)}</span>
+                                <span class="detail-stat-label">"Forks"</span>
+                            </div>
+                            <div class="detail-stat">
+                                <span class="stat">{format_number(repo.open_issues_count)}</span>
+                                <span class="detail-stat-label">"Open Issues"</span>
+                            </div>
+                            <div class="detail-stat">
+                                <span class="stat">
+                                    {move || match contributors.get() {
+                                        None => "…".to_string(),
+                                        Some(Ok(count)) => format_number(count),
+                                        Some(Err(_)) => "n/a".to_string(),
+                                    }}
+                                </span>
+                                <span class="detail-stat-label">"Contributors"</span>
+                            </div>
+                            <div class="detail-stat">
+                                <span class="date">{format_date(&repo.created_at)}</span>
+                                <span class="detail-stat-label">"Created"</span>
+                            </div>
+                            <div class="detail-stat">
+                                <span class="date">{format_date(&repo.updated_at)}</span>
+                                <span class="detail-stat-label">"Updated"</span>
+                            </div>
+                        </div>
+                    }.into_any()
+                }
+            }}
+
+            <div class="detail-grid">
+                <section class="detail-section">
+                    <h3>"Languages"</h3>
+                    {move || match languages.get() {
+                        None => view! { <p class="detail-muted">"Loading..."</p> }.into_any(),
+                        Some(Err(e)) => view! { <p class="detail-muted">{e}</p> }.into_any(),
+                        Some(Ok(langs)) if langs.is_empty() => {
+                            view! { <p class="detail-muted">"No language data."</p> }.into_any()
+                        }
+                        Some(Ok(langs)) => {
+                            let total: u64 = langs.iter().map(|(_, bytes)| bytes).sum();
+                            let rows: Vec<_> = langs
+                                .into_iter()
+                                .enumerate()
+                                .map(|(i, (lang, bytes))| {
+                                    let percent = bytes as f64 / total as f64 * 100.0;
+                                    (lang, percent, LANGUAGE_COLORS[i % LANGUAGE_COLORS.len()])
+                                })
+                                .collect();
+                            view! {
+                                <div class="language-bar">
+                                    {rows.iter().map(|(lang, percent, color)| view! {
+                                        <span
+                                            class="language-segment"
+                                            title=lang.clone()
+                                            style=format!("width: {:.2}%; background: {}", percent, color)
+                                        ></span>
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                                <ul class="language-list">
+                                    {rows.into_iter().map(|(lang, percent, color)| view! {
+                                        <li>
+                                            <span class="language-dot" style=format!("background: {}", color)></span>
+                                            <span class="language-name">{lang}</span>
+                                            <span class="detail-muted">{format!("{:.1}%", percent)}</span>
+                                        </li>
+                                    }).collect::<Vec<_>>()}
+                                </ul>
+                            }.into_any()
+                        }
+                    }}
+                </section>
+
+                <section class="detail-section">
+                    <h3>"Latest Release"</h3>
+                    {move || match release.get() {
+                        None => view! { <p class="detail-muted">"Loading..."</p> }.into_any(),
+                        Some(Err(e)) => view! { <p class="detail-muted">{e}</p> }.into_any(),
+                        Some(Ok(None)) => view! { <p class="detail-muted">"No releases published."</p> }.into_any(),
+                        Some(Ok(Some(release))) => {
+                            let title = release.name.clone().filter(|n| !n.is_empty()).unwrap_or_else(|| release.tag_name.clone());
+                            view! {
+                                <a href=release.html_url.clone() target="_blank" class="repo-name">{title}</a>
+                                <p class="detail-muted">
+                                    {release.tag_name.clone()}
+                                    {release.published_at.as_deref().map(|d| format!(" · {}", format_date(d)))}
+                                </p>
+                            }.into_any()
+                        }
+                    }}
+                </section>
+
+                <section class="detail-section">
+                    <h3>"Recent Commit Activity"</h3>
+                    {move || match activity.get() {
+                        None => view! { <p class="detail-muted">"Loading..."</p> }.into_any(),
+                        Some(Err(e)) => view! { <p class="detail-muted">{e}</p> }.into_any(),
+                        Some(Ok(None)) => view! {
+                            <p class="detail-muted">"GitHub is still computing statistics for this repository. Check back shortly."</p>
+                        }.into_any(),
+                        Some(Ok(Some(weeks))) => {
+                            let year_total: u32 = weeks.iter().map(|w| w.total).sum();
+                            let recent: Vec<_> = weeks.into_iter().rev().take(ACTIVITY_WEEKS).rev().collect();
+                            let max = recent.iter().map(|w| w.total).max().unwrap_or(0).max(1);
+                            view! {
+                                <div class="activity-chart">
+                                    {recent.into_iter().map(|w| {
+                                        let height = w.total as f64 / max as f64 * 100.0;
+                                        view! {
+                                            <div
+                                                class="activity-bar"
+                                                title=format!("Week of {}: {} commits", format_week(w.week), w.total)
+                                                style=format!("height: {:.0}%", height.max(2.0))
+                                            ></div>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
+                                <p class="detail-muted">
+                                    {format!("{} commits in the last year, last {} weeks shown", format_number(year_total), ACTIVITY_WEEKS)}
+                                </p>
+                            }.into_any()
+                        }
+                    }}
+                </section>
+            </div>
+
+            <section class="detail-section readme">
+                <h3>"README"</h3>
+                {move || match readme.get() {
+                    None => view! { <p class="detail-muted">"Loading..."</p> }.into_any(),
+                    Some(Err(e)) => view! { <p class="detail-muted">{e}</p> }.into_any(),
+                    Some(Ok(None)) => view! { <p class="detail-muted">"This repository has no README."</p> }.into_any(),
+                    // GitHub sanitizes the HTML it renders for READMEs
+                    Some(Ok(Some(html))) => view! { <article class="readme-body" inner_html=html></article> }.into_any(),
+                }}
+            </section>
+        </div>
+    }
+}
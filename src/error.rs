@@ -0,0 +1,269 @@
+// Typed search failures, and the banner that reacts to each kind
+
+use std::fmt;
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::format_reset_time;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchError {
+    /// The search quota is used up until `reset` (Unix seconds; 0 if the forge didn't say)
+    RateLimited { reset: u64 },
+    /// GitHub's abuse protection kicked in; the request may be retried after `retry_after` seconds
+    SecondaryRateLimited { retry_after: u64 },
+    /// The forge rejected the query (422), with its validation messages
+    InvalidQuery { details: Vec<String> },
+    /// The access token was rejected
+    Unauthorized,
+    /// The request never got a response, e.g. offline or blocked by CORS
+    Network(String),
+    /// The response wasn't the JSON we expected
+    Decode(String),
+    /// Any other unsuccessful status
+    Http(u16),
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchError::RateLimited { reset: 0 } => {
+                write!(f, "Rate limit exceeded. Please try again later.")
+            }
+            SearchError::RateLimited { reset } => write!(
+                f,
+                "Rate limit exceeded. Resets at {}.",
+                format_reset_time(*reset)
+            ),
+            SearchError::SecondaryRateLimited { retry_after } => write!(
+                f,
+                "Too many searches in a short time. Try again in {} seconds.",
+                retry_after
+            ),
+            SearchError::InvalidQuery { details } if details.is_empty() => write!(
+                f,
+                "Search query too complex or invalid. Try simplifying your search."
+            ),
+            SearchError::InvalidQuery { details } => {
+                write!(f, "Invalid search query: {}", details.join(" "))
+            }
+            SearchError::Unauthorized => write!(
+                f,
+                "The access token was rejected (401 Unauthorized). Check or clear the token in Settings."
+            ),
+            SearchError::Network(e) => write!(f, "Request failed: {}", e),
+            SearchError::Decode(e) => write!(f, "Failed to parse response: {}", e),
+            SearchError::Http(403) => write!(
+                f,
+                "Access forbidden (403). The server may require a token with access to this search."
+            ),
+            SearchError::Http(404) => write!(f, "Not found (404)."),
+            SearchError::Http(status) => write!(f, "API error: {}", status),
+        }
+    }
+}
+
+impl From<reqwasm::Error> for SearchError {
+    fn from(e: reqwasm::Error) -> Self {
+        match e {
+            reqwasm::Error::SerdeError(e) => SearchError::Decode(e.to_string()),
+            e => SearchError::Network(e.to_string()),
+        }
+    }
+}
+
+impl SearchError {
+    /// Seconds to wait before the same request can succeed, for the rate limit variants
+    pub fn wait_seconds(&self) -> Option<u64> {
+        match self {
+            SearchError::RateLimited { reset } if *reset > 0 => {
+                let now = (js_sys::Date::now() / 1000.0) as u64;
+                Some(reset.saturating_sub(now) + 1)
+            }
+            SearchError::RateLimited { .. } => Some(60),
+            SearchError::SecondaryRateLimited { retry_after } => Some(*retry_after),
+            _ => None,
+        }
+    }
+}
+
+/// Classifies an unsuccessful browser response; see `error_from_parts`
+pub async fn error_from_response(response: reqwasm::http::Response) -> SearchError {
+    let headers = response.headers();
+    let body = response.text().await.unwrap_or_default();
+    error_from_parts(response.status(), |name| headers.get(name), &body)
+}
+
+/// Classifies an unsuccessful response from its status, headers and body. Forges signal rate
+/// limits with 403 or 429 plus either `retry-after` (GitHub's secondary limit) or an exhausted
+/// remaining-quota header; a 403 without either is a permissions problem, e.g. on GitHub
+/// Enterprise with rate limiting off.
+pub fn error_from_parts(
+    status: u16,
+    header: impl Fn(&str) -> Option<String>,
+    body: &str,
+) -> SearchError {
+    let header = |name: &str| header(name).and_then(|v| v.trim().parse::<u64>().ok());
+
+    match status {
+        401 => SearchError::Unauthorized,
+        403 | 429 => {
+            if let Some(retry_after) = header("retry-after") {
+                return SearchError::SecondaryRateLimited { retry_after };
+            }
+            let remaining =
+                header("x-ratelimit-remaining").or_else(|| header("ratelimit-remaining"));
+            let reset = header("x-ratelimit-reset")
+                .or_else(|| header("ratelimit-reset"))
+                .unwrap_or(0);
+            if remaining == Some(0) {
+                return SearchError::RateLimited { reset };
+            }
+
+            let body = body.to_lowercase();
+            if body.contains("secondary rate limit") {
+                SearchError::SecondaryRateLimited { retry_after: 60 }
+            } else if status == 429 || body.contains("rate limit") {
+                SearchError::RateLimited { reset }
+            } else {
+                SearchError::Http(status)
+            }
+        }
+        422 => SearchError::InvalidQuery {
+            details: invalid_query_details(body),
+        },
+        status => SearchError::Http(status),
+    }
+}
+
+/// Extracts the validation messages from a 422 response body
+fn invalid_query_details(body: &str) -> Vec<String> {
+    #[derive(serde::Deserialize)]
+    struct ValidationBody {
+        message: Option<String>,
+        #[serde(default)]
+        errors: Vec<ValidationError>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct ValidationError {
+        message: Option<String>,
+    }
+
+    let Ok(body) = serde_json::from_str::<ValidationBody>(body) else {
+        return Vec::new();
+    };
+    let details: Vec<String> = body.errors.into_iter().filter_map(|e| e.message).collect();
+    if details.is_empty() {
+        body.message.into_iter().collect()
+    } else {
+        details
+    }
+}
+
+/// The parts of a message between double quotes or backticks, e.g. `language:foo` in
+/// `"language:foo" is not a recognized language`; single quotes double as apostrophes
+fn quoted_parts(message: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = message;
+    while let Some(start) = rest.find(['"', '`']) {
+        let quote = rest[start..].chars().next().unwrap_or('"');
+        let after = &rest[start + 1..];
+        let Some(len) = after.find(quote) else {
+            break;
+        };
+        parts.push(&after[..len]);
+        rest = &after[len + 1..];
+    }
+    parts
+}
+
+/// Splits a search query into (term, offending) pairs, marking terms a validation message quotes.
+/// Only whole quoted terms count, so common words elsewhere in the message aren't marked.
+fn mark_offending_terms(query: &str, details: &[String]) -> Vec<(String, bool)> {
+    let quoted: Vec<&str> = details
+        .iter()
+        .flat_map(|d| quoted_parts(d))
+        .flat_map(str::split_whitespace)
+        .collect();
+    query
+        .split_whitespace()
+        .map(|term| {
+            let offending = quoted.contains(&term);
+            (term.to_string(), offending)
+        })
+        .collect()
+}
+
+#[component]
+pub fn SearchErrorBanner(
+    error: SearchError,
+    /// The query string sent to the forge, for pointing at the offending part of a 422
+    query: String,
+    on_retry: impl Fn() + Copy + Send + Sync + 'static,
+    on_open_settings: impl Fn() + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let message = error.to_string();
+
+    match error {
+        SearchError::RateLimited { .. } | SearchError::SecondaryRateLimited { .. } => {
+            // Count down to when the quota is back, then search again by ourselves
+            let wait = error.wait_seconds().unwrap_or(60);
+            let (remaining, set_remaining) = signal(wait);
+            if let Ok(handle) = set_interval_with_handle(
+                move || {
+                    let before = remaining.get_untracked();
+                    if before == 0 {
+                        return;
+                    }
+                    set_remaining.set(before - 1);
+                    if before == 1 {
+                        on_retry();
+                    }
+                },
+                Duration::from_secs(1),
+            ) {
+                on_cleanup(move || handle.clear());
+            }
+
+            view! {
+                <div class="error">
+                    <strong>"Rate limited: "</strong>{message}
+                    <span class="retry-countdown">
+                        {move || format!(" Retrying automatically in {}s.", remaining.get())}
+                    </span>
+                </div>
+            }
+            .into_any()
+        }
+        SearchError::InvalidQuery { details } => {
+            let terms = mark_offending_terms(&query, &details);
+            view! {
+                <div class="error">
+                    <strong>"Error: "</strong>{message}
+                    <p class="query-terms">
+                        {terms.into_iter().map(|(term, offending)| view! {
+                            <code class="query-term" class:offending=offending>{term}</code>
+                        }).collect::<Vec<_>>()}
+                    </p>
+                </div>
+            }
+            .into_any()
+        }
+        SearchError::Unauthorized => view! {
+            <div class="error">
+                <strong>"Error: "</strong>{message}
+                <button class="toggle-btn" on:click=move |_| on_open_settings()>"Open Settings"</button>
+            </div>
+        }
+        .into_any(),
+        SearchError::Network(_) | SearchError::Decode(_) | SearchError::Http(_) => view! {
+            <div class="error">
+                <strong>"Error: "</strong>{message}
+                <button class="toggle-btn" on:click=move |_| on_retry()>"Retry"</button>
+            </div>
+        }
+        .into_any(),
+    }
+}
@@ -0,0 +1,158 @@
+// Exporting search results to CSV, JSON and Markdown files
+
+use crate::{format_date, Repository};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn from_param(value: &str) -> Self {
+        match value {
+            "json" => ExportFormat::Json,
+            "md" => ExportFormat::Markdown,
+            _ => ExportFormat::Csv,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+
+    fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Markdown => "text/markdown",
+        }
+    }
+
+    pub fn render(&self, repos: &[Repository]) -> String {
+        match self {
+            ExportFormat::Csv => to_csv(repos),
+            ExportFormat::Json => to_json(repos),
+            ExportFormat::Markdown => to_markdown(repos),
+        }
+    }
+}
+
+const COLUMNS: &[&str] = &[
+    "full_name",
+    "url",
+    "language",
+    "stars",
+    "forks",
+    "issues",
+    "created",
+    "updated",
+    "fork",
+    "archived",
+    "topics",
+    "forge",
+];
+
+/// One row of exported values, in COLUMNS order
+fn row_values(repo: &Repository) -> Vec<String> {
+    vec![
+        repo.full_name.clone(),
+        repo.html_url.clone(),
+        repo.language.clone().unwrap_or_default(),
+        repo.stargazers_count.to_string(),
+        repo.forks_count.to_string(),
+        repo.open_issues_count.to_string(),
+        format_date(&repo.created_at),
+        format_date(&repo.updated_at),
+        repo.fork.to_string(),
+        repo.archived.to_string(),
+        repo.topics.clone().unwrap_or_default().join(" "),
+        repo.forge
+            .map(|f| f.label())
+            .unwrap_or_default()
+            .to_string(),
+    ]
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn to_csv(repos: &[Repository]) -> String {
+    let mut out = COLUMNS.join(",");
+    out.push('\n');
+    for repo in repos {
+        let row: Vec<String> = row_values(repo).iter().map(|v| csv_field(v)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Serializes the full Repository records, not just the table columns
+pub fn to_json(repos: &[Repository]) -> String {
+    serde_json::to_string_pretty(repos).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+pub fn to_markdown(repos: &[Repository]) -> String {
+    let mut out = format!("| {} |\n", COLUMNS.join(" | "));
+    out.push_str(&format!("|{}\n", " --- |".repeat(COLUMNS.len())));
+    for repo in repos {
+        let mut cells: Vec<String> = row_values(repo).iter().map(|v| markdown_cell(v)).collect();
+        // Link the repository name rather than repeating the URL as plain text
+        cells[0] = format!("[{}]({})", cells[0], repo.html_url);
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+/// Offers exported results to the user as a timestamped file download
+pub fn download(format: ExportFormat, content: &str) -> Result<(), String> {
+    let filename = format!(
+        "oss-explorer-{}.{}",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    );
+    save_file(&filename, format.mime_type(), content)
+}
+
+/// Offers `content` to the user as a file download
+pub fn save_file(filename: &str, mime_type: &str, content: &str) -> Result<(), String> {
+    use wasm_bindgen::JsCast;
+
+    let parts = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(content));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("Failed to create file: {:?}", e))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)
+        .map_err(|e| format!("Failed to create file: {:?}", e))?;
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or("No document available")?;
+    let anchor = document
+        .create_element("a")
+        .map_err(|e| format!("Failed to create link: {:?}", e))?
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .map_err(|_| "Failed to create link".to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    let _ = web_sys::Url::revoke_object_url(&url);
+    Ok(())
+}
@@ -0,0 +1,271 @@
+// `oss-explorer generate`: runs the curated search presets and writes their snapshots into the
+// built app, as JSON for the app's rate-limit fallback and as static HTML pages
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::backend::github::{MAX_PER_PAGE, MAX_RESULTS};
+use crate::cli::{GenerateArgs, TABLE_COLUMNS};
+use crate::error::SearchError;
+use crate::snapshots::{Snapshot, SnapshotEntry, SnapshotIndex, SnapshotPreset, SNAPSHOT_DIR};
+use crate::{format_date, format_number, native, repo_detail_url, Repository, SearchFilters};
+
+/// Generates every preset in the config; a failed preset is reported and left out of the index
+pub fn run(args: GenerateArgs) -> ExitCode {
+    let presets = match read_config(&args.config) {
+        Ok(presets) => presets,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let dir = Path::new(&args.dist).join(SNAPSHOT_DIR);
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        eprintln!("error: can't create {}: {}", dir.display(), e);
+        return ExitCode::FAILURE;
+    }
+
+    let generated_at = chrono::Utc::now().to_rfc3339();
+    let mut index = SnapshotIndex {
+        generated_at: generated_at.clone(),
+        snapshots: Vec::new(),
+    };
+    let mut failed = false;
+    for preset in presets {
+        let filters = resolve_filters(&preset);
+        let (total_count, items) = match fetch_top(&args, &filters, preset.limit) {
+            Ok(top) => top,
+            Err(e) => {
+                eprintln!("error: {}: {}", preset.slug, e);
+                failed = true;
+                continue;
+            }
+        };
+        let snapshot = Snapshot {
+            slug: preset.slug,
+            title: preset.title,
+            description: preset.description,
+            generated_at: generated_at.clone(),
+            filters,
+            relative_dates: preset.relative_dates,
+            total_count,
+            items,
+        };
+        if let Err(e) = write_snapshot(&dir, &snapshot) {
+            eprintln!("error: {}: {}", snapshot.slug, e);
+            failed = true;
+            continue;
+        }
+        eprintln!(
+            "{}: {} repositories",
+            snapshot.slug,
+            format_number(snapshot.items.len() as u32)
+        );
+        index.snapshots.push(SnapshotEntry {
+            slug: snapshot.slug,
+            title: snapshot.title,
+            description: snapshot.description,
+            filters: snapshot.filters,
+            relative_dates: snapshot.relative_dates,
+        });
+    }
+
+    let written = write_json(&dir.join("index.json"), &index)
+        .and_then(|_| write_file(&dir.join("index.html"), &index_page(&index)));
+    if let Err(e) = written {
+        eprintln!("error: {}", e);
+        return ExitCode::FAILURE;
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn read_config(path: &str) -> Result<Vec<SnapshotPreset>, String> {
+    let config = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let presets: Vec<SnapshotPreset> =
+        serde_json::from_str(&config).map_err(|e| format!("{}: {}", path, e))?;
+    for (i, preset) in presets.iter().enumerate() {
+        let valid_slug = !preset.slug.is_empty()
+            && preset
+                .slug
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid_slug {
+            return Err(format!(
+                "{}: preset {} needs a slug of lowercase letters, digits and dashes",
+                path,
+                i + 1
+            ));
+        }
+        if presets[..i].iter().any(|p| p.slug == preset.slug) {
+            return Err(format!("{}: duplicate slug '{}'", path, preset.slug));
+        }
+    }
+    Ok(presets)
+}
+
+/// The preset's search with its relative dates turned into today's
+fn resolve_filters(preset: &SnapshotPreset) -> SearchFilters {
+    let mut filters = preset.relative_dates.resolve(&preset.filters);
+    filters.page = 1;
+    filters.per_page = MAX_PER_PAGE;
+    filters
+}
+
+/// The first `limit` results and the search's total count
+fn fetch_top(
+    args: &GenerateArgs,
+    filters: &SearchFilters,
+    limit: u32,
+) -> Result<(u32, Vec<Repository>), SearchError> {
+    let limit = limit.min(MAX_RESULTS);
+    let mut filters = filters.clone();
+    let mut total = 0;
+    let mut items = Vec::new();
+    while (items.len() as u32) < limit {
+        let result = native::search(&args.api_url, args.token.as_deref(), &filters)?;
+        total = result.response.total_count;
+        let fetched = result.response.items.len() as u32;
+        items.extend(result.response.items);
+        if fetched < filters.per_page {
+            break;
+        }
+        filters.page += 1;
+    }
+    items.truncate(limit as usize);
+    Ok((total, items))
+}
+
+fn write_snapshot(dir: &Path, snapshot: &Snapshot) -> Result<(), String> {
+    write_json(&dir.join(format!("{}.json", snapshot.slug)), snapshot)?;
+    let page_dir = dir.join(&snapshot.slug);
+    std::fs::create_dir_all(&page_dir)
+        .map_err(|e| format!("can't create {}: {}", page_dir.display(), e))?;
+    write_file(&page_dir.join("index.html"), &snapshot_page(snapshot))
+}
+
+fn write_json(path: &Path, value: &impl serde::Serialize) -> Result<(), String> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    write_file(path, &json)
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("can't write {}: {}", path.display(), e))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Standalone pages, so they work whatever the app's bundle is named
+const PAGE_STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Helvetica,Arial,sans-serif;\
+background:#0d1117;color:#c9d1d9;margin:0 auto;max-width:1100px;padding:24px}\
+a{color:#58a6ff;text-decoration:none}a:hover{text-decoration:underline}\
+table{width:100%;border-collapse:collapse}th,td{padding:8px;border-bottom:1px solid #30363d;text-align:left}\
+td.num{text-align:right}.muted{color:#8b949e}li{margin:8px 0}";
+
+fn page(title: &str, description: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title} - OSS Explorer</title>
+<meta name="description" content="{description}">
+<meta property="og:title" content="{title}">
+<meta property="og:description" content="{description}">
+<meta property="og:type" content="website">
+<meta property="og:site_name" content="OSS Explorer">
+<style>{style}</style>
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+        title = escape(title),
+        description = escape(description),
+        style = PAGE_STYLE,
+        body = body
+    )
+}
+
+fn snapshot_page(snapshot: &Snapshot) -> String {
+    let mut rows = String::new();
+    for (rank, repo) in snapshot.items.iter().enumerate() {
+        rows.push_str(&format!(
+            "<tr><td class=\"num\">{}</td><td><a href=\"{}\">{}</a><div class=\"muted\">{}</div></td>\
+<td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>\n",
+            rank + 1,
+            escape(&repo_detail_url(repo)),
+            escape(&repo.full_name),
+            escape(repo.description.as_deref().unwrap_or("")),
+            escape(repo.language.as_deref().unwrap_or("Unknown")),
+            format_number(repo.stargazers_count),
+            format_number(repo.forks_count),
+            format_number(repo.open_issues_count),
+            escape(&format_date(&repo.created_at)),
+        ));
+    }
+    let headings: String = TABLE_COLUMNS
+        .iter()
+        .map(|heading| format!("<th>{}</th>", heading))
+        .collect();
+    let description = if snapshot.description.is_empty() {
+        format!(
+            "{} open source repositories, as of {}",
+            format_number(snapshot.items.len() as u32),
+            format_date(&snapshot.generated_at)
+        )
+    } else {
+        snapshot.description.clone()
+    };
+
+    let body = format!(
+        "<p><a href=\"/{dir}/\">All curated lists</a></p>\n<h1>{title}</h1>\n<p>{description}</p>\n\
+<p class=\"muted\">Snapshot from {date}. <a href=\"/?{query}\">Search live in OSS Explorer</a></p>\n\
+<table>\n<thead><tr><th>#</th>{headings}</tr></thead>\n<tbody>\n{rows}</tbody>\n</table>",
+        dir = SNAPSHOT_DIR,
+        title = escape(&snapshot.title),
+        description = escape(&description),
+        date = escape(&format_date(&snapshot.generated_at)),
+        query = escape(&snapshot.filters.to_query_string()),
+        headings = headings,
+        rows = rows
+    );
+    page(&snapshot.title, &description, &body)
+}
+
+fn index_page(index: &SnapshotIndex) -> String {
+    let items: String = index
+        .snapshots
+        .iter()
+        .map(|entry| {
+            format!(
+                "<li><a href=\"/{}/{}/\">{}</a> <span class=\"muted\">{}</span></li>\n",
+                SNAPSHOT_DIR,
+                entry.slug,
+                escape(&entry.title),
+                escape(&entry.description)
+            )
+        })
+        .collect();
+    let body = format!(
+        "<p><a href=\"/\">OSS Explorer</a></p>\n<h1>Curated lists</h1>\n\
+<p class=\"muted\">Updated {}</p>\n<ul>\n{}</ul>",
+        escape(&format_date(&index.generated_at)),
+        items
+    );
+    page(
+        "Curated lists",
+        "Curated lists of top open source repositories",
+        &body,
+    )
+}
@@ -0,0 +1,86 @@
+// Infinite scroll: loading the next page when the end of the results comes into view
+
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+
+use crate::storage;
+
+// localStorage flag for preferring infinite scroll over numbered pages
+const INFINITE_SCROLL_STORAGE_KEY: &str = "oss-explorer.infinite-scroll";
+
+// Start loading a little before the end of the list is actually reached
+const LOOKAHEAD_MARGIN: &str = "400px";
+
+pub fn load_preference() -> bool {
+    storage::get_item(INFINITE_SCROLL_STORAGE_KEY).is_some_and(|v| v == "1")
+}
+
+pub fn save_preference(enabled: bool) {
+    if enabled {
+        storage::set_item(INFINITE_SCROLL_STORAGE_KEY, "1");
+    } else {
+        storage::remove_item(INFINITE_SCROLL_STORAGE_KEY);
+    }
+}
+
+type ObserverCallback = Closure<dyn FnMut(js_sys::Array)>;
+
+/// An empty marker placed after the results that calls `on_visible` when scrolled near.
+/// Whenever `results` changes the marker is observed afresh, so a page too short to
+/// push it out of view still leads to the next one being loaded.
+#[component]
+pub fn ScrollSentinel(
+    on_visible: impl Fn() + Copy + 'static,
+    #[prop(into)] results: Signal<usize>,
+) -> impl IntoView {
+    let sentinel = NodeRef::<leptos::html::Div>::new();
+    let observer = StoredValue::new_local(Option::<(IntersectionObserver, ObserverCallback)>::None);
+
+    Effect::new(move |_| {
+        results.track();
+        let Some(element) = sentinel.get() else {
+            return;
+        };
+
+        let reobserved = observer.with_value(|existing| {
+            existing.as_ref().map(|(observer, _)| {
+                observer.unobserve(&element);
+                observer.observe(&element);
+            })
+        });
+        if reobserved.is_some() {
+            return;
+        }
+
+        let callback: ObserverCallback = Closure::new(move |entries: js_sys::Array| {
+            let visible = entries.iter().any(|entry| {
+                entry
+                    .unchecked_into::<IntersectionObserverEntry>()
+                    .is_intersecting()
+            });
+            if visible {
+                on_visible();
+            }
+        });
+        let options = IntersectionObserverInit::new();
+        options.set_root_margin(LOOKAHEAD_MARGIN);
+        if let Ok(new_observer) =
+            IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options)
+        {
+            new_observer.observe(&element);
+            observer.set_value(Some((new_observer, callback)));
+        }
+    });
+
+    on_cleanup(move || {
+        observer.with_value(|existing| {
+            if let Some((observer, _)) = existing {
+                observer.disconnect();
+            }
+        });
+    });
+
+    view! { <div class="scroll-sentinel" node_ref=sentinel></div> }
+}
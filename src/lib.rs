@@ -1,6 +1,57 @@
+// The search page's view type is deep enough to exceed the default limit when rendered to HTML
+#![recursion_limit = "256"]
+
+use std::sync::Arc;
+use std::time::Duration;
+
 use leptos::prelude::*;
+use leptos_meta::{provide_meta_context, Meta, Title};
+use leptos_router::components::{Route, Router, Routes, A};
+use leptos_router::hooks::{use_location, use_navigate, use_query_map};
+use leptos_router::params::ParamsMap;
+use leptos_router::path;
 use serde::{Deserialize, Serialize};
 
+mod backend;
+mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+mod detail;
+mod error;
+mod export;
+#[cfg(not(target_arch = "wasm32"))]
+mod generate;
+mod infinite_scroll;
+mod load_all;
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(not(target_arch = "wasm32"))]
+mod proxy;
+mod retry;
+mod saved_searches;
+mod snapshots;
+#[cfg(any(feature = "ssr", feature = "hydrate"))]
+mod ssr;
+mod storage;
+#[cfg(not(target_arch = "wasm32"))]
+mod tui;
+mod watchlist;
+
+use backend::github::{default_api_url, normalize_api_url};
+use backend::{
+    sort_repositories, ActiveBackend, FederatedBackend, Forge, ForgeConfig, ForgeConnection,
+    GiteaBackend, GithubBackend, GitlabBackend, SearchBackend, SelectedForge,
+};
+use detail::RepoDetail;
+use error::{SearchError, SearchErrorBanner};
+use export::ExportFormat;
+use infinite_scroll::ScrollSentinel;
+use retry::{with_retry, RetryPolicy};
+use saved_searches::{SavedSearches, SavedSearchesPanel};
+#[cfg(feature = "hydrate")]
+pub use ssr::hydrate;
+use watchlist::{WatchToggle, Watchlist, WatchlistPage};
+
 // GitHub API response structures
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Repository {
@@ -21,6 +72,9 @@ pub struct Repository {
     #[serde(default)]
     pub archived: bool,
     pub topics: Option<Vec<String>>,
+    /// The forge the repository was found on; absent in GitHub's JSON, so set after parsing
+    #[serde(default)]
+    pub forge: Option<Forge>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -47,10 +101,23 @@ pub struct RateLimitInfo {
 pub struct SearchResult {
     pub response: SearchResponse,
     pub rate_limit: Option<RateLimitInfo>,
+    /// Problems that cost some results without failing the search, e.g. one forge being down
+    pub warnings: Vec<String>,
+    /// Served from the response cache (possibly after a 304) rather than freshly fetched
+    pub cached: bool,
+}
+
+/// A search the server already ran for the page it rendered (SSR), so the first render shows
+/// its results and the hydrating browser doesn't fetch them again
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InitialSearch {
+    pub filters: SearchFilters,
+    pub response: SearchResponse,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum SortBy {
+    #[default]
     Stars,
     Forks,
     Issues,
@@ -68,10 +135,32 @@ impl SortBy {
             SortBy::Updated => "updated",
         }
     }
+
+    /// Short name used in select values and shareable URLs
+    fn as_param(&self) -> &'static str {
+        match self {
+            SortBy::Stars => "stars",
+            SortBy::Forks => "forks",
+            SortBy::Issues => "issues",
+            SortBy::Created => "created",
+            SortBy::Updated => "updated",
+        }
+    }
+
+    fn from_param(value: &str) -> Self {
+        match value {
+            "forks" => SortBy::Forks,
+            "issues" => SortBy::Issues,
+            "created" => SortBy::Created,
+            "updated" => SortBy::Updated,
+            _ => SortBy::Stars,
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum SortOrder {
+    #[default]
     Desc,
     Asc,
 }
@@ -83,25 +172,71 @@ impl SortOrder {
             SortOrder::Asc => "asc",
         }
     }
+
+    fn from_param(value: &str) -> Self {
+        if value == "asc" {
+            SortOrder::Asc
+        } else {
+            SortOrder::Desc
+        }
+    }
 }
 
 // Results per page options
 pub const PER_PAGE_OPTIONS: &[u32] = &[10, 30, 50, 100];
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum ForkFilter {
+    #[default]
     All,
     OriginalOnly,
     ForksOnly,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+impl ForkFilter {
+    fn as_param(&self) -> &'static str {
+        match self {
+            ForkFilter::All => "all",
+            ForkFilter::OriginalOnly => "original",
+            ForkFilter::ForksOnly => "forks",
+        }
+    }
+
+    fn from_param(value: &str) -> Self {
+        match value {
+            "original" => ForkFilter::OriginalOnly,
+            "forks" => ForkFilter::ForksOnly,
+            _ => ForkFilter::All,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum ArchivedFilter {
     All,
+    #[default]
     ActiveOnly,
     ArchivedOnly,
 }
 
+impl ArchivedFilter {
+    fn as_param(&self) -> &'static str {
+        match self {
+            ArchivedFilter::All => "all",
+            ArchivedFilter::ActiveOnly => "active",
+            ArchivedFilter::ArchivedOnly => "archived",
+        }
+    }
+
+    fn from_param(value: &str) -> Self {
+        match value {
+            "all" => ArchivedFilter::All,
+            "archived" => ArchivedFilter::ArchivedOnly,
+            _ => ArchivedFilter::ActiveOnly,
+        }
+    }
+}
+
 // Star range presets for the filter
 pub const STAR_RANGES: &[(&str, &str)] = &[
     ("Any", ""),
@@ -147,11 +282,79 @@ pub const LANGUAGES: &[&str] = &[
     "Markdown",
 ];
 
-#[derive(Clone, Debug, Default)]
+// Date range presets as (label, from days ago, to days ago); None leaves that end open.
+// Creation date presets are for finding new projects...
+pub const CREATED_PRESETS: &[(&str, Option<i64>, Option<i64>)] = &[
+    ("Last 7 days", Some(7), None),
+    ("Last 30 days", Some(30), None),
+    ("Last 6 months", Some(182), None),
+    ("Last year", Some(365), None),
+    ("Over 5 years ago", None, Some(5 * 365)),
+];
+
+// ...and last push presets for telling active projects from abandoned ones
+pub const PUSHED_PRESETS: &[(&str, Option<i64>, Option<i64>)] = &[
+    ("Last 7 days", Some(7), None),
+    ("Last 30 days", Some(30), None),
+    ("Last year", Some(365), None),
+    ("Not in the last year", None, Some(365)),
+    ("Not in the last 2 years", None, Some(2 * 365)),
+];
+
+/// Resolves a date preset into (from, to) dates relative to today, empty meaning unbounded
+fn resolve_date_preset(from_days_ago: Option<i64>, to_days_ago: Option<i64>) -> (String, String) {
+    (
+        from_days_ago.map(days_ago).unwrap_or_default(),
+        to_days_ago.map(days_ago).unwrap_or_default(),
+    )
+}
+
+fn days_ago(days: i64) -> String {
+    (chrono::Utc::now().date_naive() - chrono::Duration::days(days))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Accepts only YYYY-MM-DD dates, the format GitHub qualifiers and date inputs share
+fn parse_date_param(value: &str) -> Option<String> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+/// Normalizes user input into GitHub's topic format: lowercase words joined by hyphens
+fn normalize_topic(topic: &str) -> String {
+    topic
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Parses a comma-separated topic list, dropping blanks and duplicates
+fn parse_topics(value: &str) -> Vec<String> {
+    let mut topics: Vec<String> = Vec::new();
+    for topic in value.split(',').map(normalize_topic) {
+        if !topic.is_empty() && !topics.contains(&topic) {
+            topics.push(topic);
+        }
+    }
+    topics
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchFilters {
     pub query: String,
     pub language: String,
     pub min_stars: String,
+    // Date ranges as YYYY-MM-DD; an empty string leaves that end of the range open
+    pub created_from: String,
+    pub created_to: String,
+    pub pushed_from: String,
+    pub pushed_to: String,
+    // Every topic must match (GitHub ANDs repeated topic: qualifiers)
+    pub topics: Vec<String>,
     pub fork_filter: ForkFilter,
     pub archived_filter: ArchivedFilter,
     pub sort_by: SortBy,
@@ -160,35 +363,146 @@ pub struct SearchFilters {
     pub per_page: u32,
 }
 
-impl Default for ForkFilter {
+impl Default for SearchFilters {
     fn default() -> Self {
-        ForkFilter::All
+        SearchFilters {
+            query: String::new(),
+            language: "All".to_string(),
+            min_stars: String::new(),
+            created_from: String::new(),
+            created_to: String::new(),
+            pushed_from: String::new(),
+            pushed_to: String::new(),
+            topics: Vec::new(),
+            fork_filter: ForkFilter::default(),
+            archived_filter: ArchivedFilter::default(),
+            sort_by: SortBy::default(),
+            sort_order: SortOrder::default(),
+            page: 1,
+            per_page: 30,
+        }
     }
 }
 
-impl Default for ArchivedFilter {
-    fn default() -> Self {
-        ArchivedFilter::ActiveOnly
-    }
-}
+impl SearchFilters {
+    /// Serializes the filters into a URL query string, omitting fields left at their defaults
+    pub fn to_query_string(&self) -> String {
+        let defaults = SearchFilters::default();
+        let mut params: Vec<(&str, String)> = Vec::new();
 
-impl Default for SortBy {
-    fn default() -> Self {
-        SortBy::Stars
+        if !self.query.is_empty() {
+            params.push(("q", self.query.clone()));
+        }
+        if !self.language.is_empty() && self.language != defaults.language {
+            params.push(("lang", self.language.clone()));
+        }
+        if !self.min_stars.is_empty() {
+            params.push(("stars", self.min_stars.clone()));
+        }
+        for (key, value) in [
+            ("created_from", &self.created_from),
+            ("created_to", &self.created_to),
+            ("pushed_from", &self.pushed_from),
+            ("pushed_to", &self.pushed_to),
+        ] {
+            if !value.is_empty() {
+                params.push((key, value.clone()));
+            }
+        }
+        if !self.topics.is_empty() {
+            params.push(("topics", self.topics.join(",")));
+        }
+        if self.fork_filter != defaults.fork_filter {
+            params.push(("fork", self.fork_filter.as_param().to_string()));
+        }
+        if self.archived_filter != defaults.archived_filter {
+            params.push(("archived", self.archived_filter.as_param().to_string()));
+        }
+        if self.sort_by != defaults.sort_by {
+            params.push(("sort", self.sort_by.as_param().to_string()));
+        }
+        if self.sort_order != defaults.sort_order {
+            params.push(("order", self.sort_order.as_str().to_string()));
+        }
+        if self.page != defaults.page {
+            params.push(("page", self.page.to_string()));
+        }
+        if self.per_page != defaults.per_page {
+            params.push(("per_page", self.per_page.to_string()));
+        }
+
+        params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, urlencoding(value)))
+            .collect::<Vec<_>>()
+            .join("&")
     }
-}
 
-impl Default for SortOrder {
-    fn default() -> Self {
-        SortOrder::Desc
+    /// Restores filters from URL query parameters; missing or invalid values fall back to defaults
+    pub fn from_query_map(params: &ParamsMap) -> Self {
+        let defaults = SearchFilters::default();
+        let date_param = |key: &str| {
+            params
+                .get_str(key)
+                .and_then(parse_date_param)
+                .unwrap_or_default()
+        };
+        SearchFilters {
+            query: params.get("q").unwrap_or_default(),
+            language: params.get("lang").unwrap_or(defaults.language),
+            min_stars: params.get("stars").unwrap_or_default(),
+            created_from: date_param("created_from"),
+            created_to: date_param("created_to"),
+            pushed_from: date_param("pushed_from"),
+            pushed_to: date_param("pushed_to"),
+            topics: params
+                .get_str("topics")
+                .map(parse_topics)
+                .unwrap_or_default(),
+            fork_filter: params
+                .get_str("fork")
+                .map(ForkFilter::from_param)
+                .unwrap_or_default(),
+            archived_filter: params
+                .get_str("archived")
+                .map(ArchivedFilter::from_param)
+                .unwrap_or_default(),
+            sort_by: params
+                .get_str("sort")
+                .map(SortBy::from_param)
+                .unwrap_or_default(),
+            sort_order: params
+                .get_str("order")
+                .map(SortOrder::from_param)
+                .unwrap_or_default(),
+            page: params
+                .get_str("page")
+                .and_then(|p| p.parse().ok())
+                .filter(|&p| p >= 1)
+                .unwrap_or(defaults.page),
+            per_page: params
+                .get_str("per_page")
+                .and_then(|p| p.parse().ok())
+                .filter(|p| PER_PAGE_OPTIONS.contains(p))
+                .unwrap_or(defaults.per_page),
+        }
     }
 }
 
 fn build_search_query(filters: &SearchFilters) -> String {
     let mut parts = Vec::new();
 
+    let has_date_range = !(filters.created_from.is_empty()
+        && filters.created_to.is_empty()
+        && filters.pushed_from.is_empty()
+        && filters.pushed_to.is_empty());
+
     // Add user query or default
-    if filters.query.is_empty() && filters.min_stars.is_empty() {
+    if filters.query.is_empty()
+        && filters.min_stars.is_empty()
+        && !has_date_range
+        && filters.topics.is_empty()
+    {
         parts.push("stars:>100".to_string());
     } else if !filters.query.is_empty() {
         parts.push(filters.query.clone());
@@ -204,6 +518,20 @@ fn build_search_query(filters: &SearchFilters) -> String {
         parts.push(format!("stars:{}", filters.min_stars));
     }
 
+    // Add topic filters
+    for topic in &filters.topics {
+        parts.push(format!("topic:{}", topic));
+    }
+
+    // Add creation and last push date ranges
+    if let Some(range) = date_range_qualifier("created", &filters.created_from, &filters.created_to)
+    {
+        parts.push(range);
+    }
+    if let Some(range) = date_range_qualifier("pushed", &filters.pushed_from, &filters.pushed_to) {
+        parts.push(range);
+    }
+
     // Add fork filter
     match filters.fork_filter {
         ForkFilter::All => {}
@@ -221,96 +549,86 @@ fn build_search_query(filters: &SearchFilters) -> String {
     parts.join(" ")
 }
 
-async fn search_repositories(filters: SearchFilters) -> Result<SearchResult, String> {
-    let search_query = build_search_query(&filters);
-
-    let url = format!(
-        "https://api.github.com/search/repositories?q={}&sort={}&order={}&per_page={}&page={}",
-        urlencoding(&search_query),
-        filters.sort_by.as_str(),
-        filters.sort_order.as_str(),
-        filters.per_page,
-        filters.page
-    );
-
-    let response = reqwasm::http::Request::get(&url)
-        .header("Accept", "application/vnd.github.v3+json")
-        .header("User-Agent", "oss-explorer")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {:?}", e))?;
-
-    // Extract rate limit headers
-    let rate_limit = extract_rate_limit_info(&response);
-
-    if response.status() == 403 {
-        if let Some(ref rl) = rate_limit {
-            if rl.remaining == 0 {
-                let reset_time = format_reset_time(rl.reset_timestamp);
-                return Err(format!(
-                    "Rate limit exceeded. Resets at {}. Try again later.",
-                    reset_time
-                ));
+// localStorage key for the user-supplied GitHub personal access token
+const TOKEN_STORAGE_KEY: &str = "oss-explorer.github-token";
+
+// localStorage key for a runtime override of the GitHub (Enterprise) API base URL
+const API_URL_STORAGE_KEY: &str = "oss-explorer.github-api-url";
+
+/// The user's GitHub personal access token, shared with every page through context
+#[derive(Clone, Copy)]
+struct GithubToken(RwSignal<String>);
+
+impl GithubToken {
+    fn get_untracked(&self) -> Option<String> {
+        Some(self.0.get_untracked()).filter(|t| !t.is_empty())
+    }
+}
+
+/// The GitHub or GitHub Enterprise API base URL in use, shared with every page through context
+#[derive(Clone, Copy)]
+struct GithubApiUrl(RwSignal<String>);
+
+/// The settings every forge's backend is built from, shared through context so pages can
+/// reach the forge a particular repository came from
+#[derive(Clone, Copy)]
+struct ForgeBackends {
+    github_token: GithubToken,
+    api_url: GithubApiUrl,
+    config: ForgeConfig,
+}
+
+impl ForgeBackends {
+    /// Builds the backend for one forge, tracking its settings so it can back a derived signal
+    fn get(&self, forge: Forge) -> Arc<dyn SearchBackend> {
+        match forge {
+            Forge::Github => {
+                let token = Some(self.github_token.0.get()).filter(|t| !t.is_empty());
+                Arc::new(GithubBackend::new(self.api_url.0.get(), token))
+            }
+            Forge::Gitlab => {
+                let gitlab = self.config.0.with(|c| c.gitlab.clone());
+                Arc::new(GitlabBackend::new(&gitlab.url, gitlab.token()))
+            }
+            Forge::Gitea => {
+                let gitea = self.config.0.with(|c| c.gitea.clone());
+                Arc::new(GiteaBackend::new(&gitea.url, gitea.token()))
             }
         }
-        return Err("Rate limit exceeded. Please try again later.".to_string());
     }
 
-    if response.status() == 422 {
-        return Err("Search query too complex or invalid. Try simplifying your search.".to_string());
+    fn get_untracked(&self, forge: Forge) -> Arc<dyn SearchBackend> {
+        untrack(|| self.get(forge))
     }
+}
 
-    if !response.ok() {
-        return Err(format!("GitHub API error: {}", response.status()));
+/// Link to a repository's detail page, naming its forge unless it's the default
+fn repo_detail_url(repo: &Repository) -> String {
+    match repo.forge.filter(|&f| f != Forge::default()) {
+        Some(forge) => format!("/repo/{}?forge={}", repo.full_name, forge.as_param()),
+        None => format!("/repo/{}", repo.full_name),
     }
-
-    let search_response = response
-        .json::<SearchResponse>()
-        .await
-        .map_err(|e| format!("Failed to parse response: {:?}", e))?;
-
-    Ok(SearchResult {
-        response: search_response,
-        rate_limit,
-    })
 }
 
-fn extract_rate_limit_info(response: &reqwasm::http::Response) -> Option<RateLimitInfo> {
-    let limit = response
-        .headers()
-        .get("x-ratelimit-limit")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
-    let remaining = response
-        .headers()
-        .get("x-ratelimit-remaining")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
-    let reset_timestamp = response
-        .headers()
-        .get("x-ratelimit-reset")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
-
-    if limit > 0 {
-        Some(RateLimitInfo {
-            limit,
-            remaining,
-            reset_timestamp,
-        })
-    } else {
-        None
+/// Builds a GitHub range qualifier such as `created:2024-01-01..2024-06-30` or `pushed:<=2023-01-01`
+fn date_range_qualifier(name: &str, from: &str, to: &str) -> Option<String> {
+    match (from.is_empty(), to.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(format!("{}:>={}", name, from)),
+        (true, false) => Some(format!("{}:<={}", name, to)),
+        (false, false) => Some(format!("{}:{}..{}", name, from, to)),
     }
 }
 
 fn format_reset_time(timestamp: u64) -> String {
-    // Convert Unix timestamp to a readable format
-    // Since we're in WASM, we'll use JS Date via web-sys
-    use wasm_bindgen::JsValue;
-    let date = js_sys::Date::new(&JsValue::from_f64(timestamp as f64 * 1000.0));
-    let hours = date.get_hours();
-    let minutes = date.get_minutes();
-    format!("{:02}:{:02}", hours, minutes)
+    // Local time of day; chrono reads the time zone from JS Date in the browser
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
 }
 
 fn urlencoding(s: &str) -> String {
@@ -352,12 +670,37 @@ fn format_number(n: u32) -> String {
     }
 }
 
-fn calculate_total_pages(total_count: u32, per_page: u32) -> u32 {
-    // GitHub API limits to 1000 results max
-    let effective_total = total_count.min(1000);
-    (effective_total + per_page - 1) / per_page
+/// Page title describing a search, e.g. "Rust repositories with 1K+ stars tagged cli"
+fn search_title(filters: &SearchFilters) -> String {
+    let mut title = match filters.language.as_str() {
+        "All" | "" => "Repositories".to_string(),
+        language => format!("{} repositories", language),
+    };
+    if !filters.query.is_empty() {
+        title.push_str(&format!(" matching \"{}\"", filters.query));
+    }
+    if !filters.min_stars.is_empty() {
+        let stars = STAR_RANGES
+            .iter()
+            .find(|(_, value)| *value == filters.min_stars)
+            .map_or(filters.min_stars.as_str(), |(label, _)| label);
+        title.push_str(&format!(" with {} stars", stars));
+    }
+    if !filters.topics.is_empty() {
+        title.push_str(&format!(" tagged {}", filters.topics.join(", ")));
+    }
+    title
 }
 
+fn calculate_total_pages(total_count: u32, per_page: u32, max_results: Option<u32>) -> u32 {
+    // GitHub caps search results (see its backend); other forges can page through everything
+    let effective_total = total_count.min(max_results.unwrap_or(u32::MAX));
+    effective_total.div_ceil(per_page)
+}
+
+// Pause in typing after which the query box searches by itself
+const QUERY_DEBOUNCE_MS: u64 = 400;
+
 // Default avatar as a data URI (simple gray circle with user icon)
 const DEFAULT_AVATAR: &str = "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 40 40'%3E%3Ccircle cx='20' cy='20' r='20' fill='%2330363d'/%3E%3Ccircle cx='20' cy='16' r='7' fill='%238b949e'/%3E%3Cpath d='M6 36c0-8 6-14 14-14s14 6 14 14' fill='%238b949e'/%3E%3C/svg%3E";
 
@@ -381,9 +724,207 @@ fn get_safe_avatar_url(url: &str) -> String {
 
 #[component]
 pub fn App() -> impl IntoView {
+    provide_meta_context();
+    let github_token = GithubToken(RwSignal::new(
+        storage::get_item(TOKEN_STORAGE_KEY).unwrap_or_default(),
+    ));
+    let api_url = GithubApiUrl(RwSignal::new(
+        storage::get_item(API_URL_STORAGE_KEY)
+            .map(|url| normalize_api_url(&url))
+            .unwrap_or_else(default_api_url),
+    ));
+    let selected_forge = SelectedForge::load();
+    let forge_config = ForgeConfig::load();
+    provide_context(github_token);
+    provide_context(api_url);
+    provide_context(selected_forge);
+    provide_context(forge_config);
+    let forge_backends = ForgeBackends {
+        github_token,
+        api_url,
+        config: forge_config,
+    };
+    provide_context(forge_backends);
+    provide_context(ActiveBackend(Signal::derive(
+        move || match selected_forge.0.get() {
+            Some(forge) => forge_backends.get(forge),
+            None => Arc::new(FederatedBackend::new(
+                Forge::ALL.iter().map(|&f| forge_backends.get(f)).collect(),
+            )),
+        },
+    )));
+    let backend = expect_context::<ActiveBackend>();
+    let watchlist = Watchlist::load();
+    provide_context(watchlist);
+    provide_context(SavedSearches::load());
+
+    view! {
+        <Title formatter=|text: String| format!("{} - OSS Explorer", text) text="Discover Open Source Software"/>
+        <Router>
+            <div class="app">
+                <header>
+                    <h1>"OSS Explorer"</h1>
+                    <p class="subtitle">"Discover open source projects by language, creation date, and activity"</p>
+                    <nav class="main-nav">
+                        <A href="/">"Search"</A>
+                        <A href="/watchlist">{move || format!("Watchlist ({})", watchlist.len())}</A>
+                        <select
+                            class="forge-select"
+                            title="Forge to search"
+                            on:change=move |ev| {
+                                let value = event_target_value(&ev);
+                                selected_forge.set((value != "all").then(|| Forge::from_param(&value)));
+                            }
+                        >
+                            {Forge::ALL.iter().map(|&forge| view! {
+                                <option value=forge.as_param() selected=move || selected_forge.0.get() == Some(forge)>
+                                    {forge.label()}
+                                </option>
+                            }).collect::<Vec<_>>()}
+                            <option value="all" selected=move || selected_forge.0.get().is_none()>"All forges"</option>
+                        </select>
+                    </nav>
+                </header>
+
+                <Routes fallback=|| view! { <div class="empty">"Page not found."</div> }>
+                    <Route path=path!("") view=SearchPage/>
+                    <Route path=path!("/repo/*full_name") view=RepoDetail/>
+                    <Route path=path!("/watchlist") view=WatchlistPage/>
+                </Routes>
+
+                <footer>
+                    <p>
+                        "Powered by the "
+                        <a href=move || backend.0.get().web_url() target="_blank">
+                            {move || format!("{} API", backend.0.get().name())}
+                        </a>
+                        " | Built with Rust + Leptos"
+                    </p>
+                </footer>
+            </div>
+        </Router>
+    }
+}
+
+/// URL and token inputs for one of the non-GitHub forges in the settings panel
+#[component]
+fn ForgeConnectionFields(
+    label: &'static str,
+    placeholder: &'static str,
+    connection: ForgeConnection,
+    on_save: impl Fn(ForgeConnection) + Copy + 'static,
+) -> impl IntoView {
+    let (url, set_url) = signal(connection.url);
+    let (token, set_token) = signal(connection.token);
+
+    view! {
+        <div class="filter-group token-group">
+            <label>{label}</label>
+            <input
+                type="url"
+                placeholder=placeholder
+                prop:value=move || url.get()
+                on:input=move |ev| set_url.set(event_target_value(&ev))
+            />
+            <input
+                type="password"
+                placeholder="Access token (optional)"
+                prop:value=move || token.get()
+                on:input=move |ev| set_token.set(event_target_value(&ev))
+            />
+            <button class="toggle-btn" on:click=move |_| on_save(ForgeConnection {
+                url: url.get_untracked().trim().trim_end_matches('/').to_string(),
+                token: token.get_untracked().trim().to_string(),
+            })>
+                "Save"
+            </button>
+        </div>
+    }
+}
+
+/// A preset dropdown plus from/to date pickers for one of the date range filters
+#[component]
+fn DateRangeFilter(
+    label: &'static str,
+    presets: &'static [(&'static str, Option<i64>, Option<i64>)],
+    from: ReadSignal<String>,
+    set_from: WriteSignal<String>,
+    to: ReadSignal<String>,
+    set_to: WriteSignal<String>,
+    on_change: impl Fn() + Copy + 'static,
+) -> impl IntoView {
+    // The preset whose resolved range matches the current dates, if any
+    let active_preset = move || {
+        let current = (from.get(), to.get());
+        presets
+            .iter()
+            .position(|&(_, from_days, to_days)| resolve_date_preset(from_days, to_days) == current)
+    };
+
+    view! {
+        <div class="filter-group date-range">
+            <label>{label}</label>
+            <select on:change=move |ev| {
+                let value = event_target_value(&ev);
+                let (new_from, new_to) = match value.parse::<usize>().ok().and_then(|i| presets.get(i)) {
+                    Some(&(_, from_days, to_days)) => resolve_date_preset(from_days, to_days),
+                    None if value == "any" => (String::new(), String::new()),
+                    // "Custom" keeps whatever the date pickers hold
+                    None => return,
+                };
+                set_from.set(new_from);
+                set_to.set(new_to);
+                on_change();
+            }>
+                <option value="any" selected=move || from.get().is_empty() && to.get().is_empty()>"Any time"</option>
+                {presets.iter().enumerate().map(|(i, (preset_label, _, _))| {
+                    view! {
+                        <option value=i.to_string() selected=move || active_preset() == Some(i)>
+                            {*preset_label}
+                        </option>
+                    }
+                }).collect::<Vec<_>>()}
+                <option
+                    value="custom"
+                    selected=move || active_preset().is_none() && !(from.get().is_empty() && to.get().is_empty())
+                >
+                    "Custom"
+                </option>
+            </select>
+            <input
+                type="date"
+                title="From"
+                prop:value=move || from.get()
+                on:change=move |ev| {
+                    set_from.set(event_target_value(&ev));
+                    on_change();
+                }
+            />
+            <span class="date-separator">"to"</span>
+            <input
+                type="date"
+                title="To"
+                prop:value=move || to.get()
+                on:change=move |ev| {
+                    set_to.set(event_target_value(&ev));
+                    on_change();
+                }
+            />
+        </div>
+    }
+}
+
+#[component]
+fn SearchPage() -> impl IntoView {
     let (query, set_query) = signal(String::new());
     let (language, set_language) = signal("All".to_string());
     let (min_stars, set_min_stars) = signal(String::new());
+    let (created_from, set_created_from) = signal(String::new());
+    let (created_to, set_created_to) = signal(String::new());
+    let (pushed_from, set_pushed_from) = signal(String::new());
+    let (pushed_to, set_pushed_to) = signal(String::new());
+    let (topics, set_topics) = signal(Vec::<String>::new());
+    let (topic_input, set_topic_input) = signal(String::new());
     let (fork_filter, set_fork_filter) = signal(ForkFilter::All);
     let (archived_filter, set_archived_filter) = signal(ArchivedFilter::ActiveOnly);
     let (sort_by, set_sort_by) = signal(SortBy::Stars);
@@ -391,47 +932,260 @@ pub fn App() -> impl IntoView {
     let (repositories, set_repositories) = signal(Vec::<Repository>::new());
     let (loading, set_loading) = signal(false);
     let (error, set_error) = signal(Option::<String>::None);
+    // The failed search's error, with the query it sent for pointing out invalid qualifiers
+    let (search_error, set_search_error) = signal(Option::<(SearchError, String)>::None);
+    // Seconds until a failed search is retried, while waiting to retry
+    let (retry_in, set_retry_in) = signal(Option::<u64>::None);
     let (total_count, set_total_count) = signal(0u32);
     let (current_page, set_current_page) = signal(1u32);
     let (per_page, set_per_page) = signal(30u32);
     let (rate_limit, set_rate_limit) = signal(Option::<RateLimitInfo>::None);
+    let (from_cache, set_from_cache) = signal(false);
+    let search_generation = StoredValue::new(0u64);
+    let (persistent_cache, set_persistent_cache) = signal(cache::persistent_enabled());
     let (incomplete_results, set_incomplete_results) = signal(false);
+    let (warnings, set_warnings) = signal(Vec::<String>::new());
+    // Set while a curated snapshot stands in for a rate-limited search: its title and date
+    let (snapshot_used, set_snapshot_used) = signal(Option::<(String, String)>::None);
     let (show_advanced, set_show_advanced) = signal(false);
+    let (show_settings, set_show_settings) = signal(false);
+    let (show_saved, set_show_saved) = signal(false);
+    let github_token = expect_context::<GithubToken>();
+    let backend = expect_context::<ActiveBackend>();
+    let (token_input, set_token_input) = signal(github_token.0.get_untracked());
+    let api_url = expect_context::<GithubApiUrl>();
+    let (api_url_input, set_api_url_input) = signal(api_url.0.get_untracked());
+    let selected_forge = expect_context::<SelectedForge>();
+    let forge_config = expect_context::<ForgeConfig>();
+
+    let (export_format, set_export_format) = signal(ExportFormat::Csv);
+    let (export_all, set_export_all) = signal(false);
+    let (export_progress, set_export_progress) = signal(Option::<String>::None);
+
+    // Every page of the current search, once "Load all" has fetched them; None while paging
+    let (loaded, set_loaded) = signal(Option::<Vec<Repository>>::None);
+    let (load_progress, set_load_progress) = signal(Option::<String>::None);
+    let (local_filter, set_local_filter) = signal(String::new());
+    // Whether "Load all" splits the search into creation date windows to get past the result cap
+    let (slice_by_date, set_slice_by_date) = signal(false);
+    let (infinite_scroll, set_infinite_scroll) = signal(infinite_scroll::load_preference());
+
+    let query_map = use_query_map();
+    let location = use_location();
+    let navigate = StoredValue::new(use_navigate());
+
+    // Sets the filter controls to match a search
+    let restore_controls = move |filters: &SearchFilters| {
+        set_query.set(filters.query.clone());
+        set_language.set(filters.language.clone());
+        set_min_stars.set(filters.min_stars.clone());
+        set_created_from.set(filters.created_from.clone());
+        set_created_to.set(filters.created_to.clone());
+        set_pushed_from.set(filters.pushed_from.clone());
+        set_pushed_to.set(filters.pushed_to.clone());
+        set_topics.set(filters.topics.clone());
+        set_fork_filter.set(filters.fork_filter);
+        set_archived_filter.set(filters.archived_filter);
+        set_sort_by.set(filters.sort_by);
+        set_sort_order.set(filters.sort_order);
+        set_per_page.set(filters.per_page);
+    };
 
-    let total_pages = move || calculate_total_pages(total_count.get(), per_page.get());
+    // Render the URL's search straight away; on the server no effect runs to do it, and when
+    // hydrating, the results the server rendered must be there from the start
+    let url_filters = SearchFilters::from_query_map(&query_map.get_untracked());
+    restore_controls(&url_filters);
+    let initial = use_context::<InitialSearch>().filter(|initial| initial.filters == url_filters);
+    let skip_first_search = StoredValue::new(initial.is_some());
+    if let Some(initial) = initial {
+        set_total_count.set(initial.response.total_count);
+        set_incomplete_results.set(initial.response.incomplete_results);
+        set_repositories.set(initial.response.items);
+        set_current_page.set(url_filters.page);
+    }
 
-    let do_search = move |page: u32| {
-        let filters = SearchFilters {
-            query: query.get(),
-            language: language.get(),
-            min_stars: min_stars.get(),
-            fork_filter: fork_filter.get(),
-            archived_filter: archived_filter.get(),
-            sort_by: sort_by.get(),
-            sort_order: sort_order.get(),
-            page,
-            per_page: per_page.get(),
-        };
+    // Pages are as large as the forge returns, which can be smaller than the size chosen
+    let total_pages = move || {
+        let backend = backend.0.get();
+        calculate_total_pages(
+            total_count.get(),
+            per_page.get().min(backend.max_per_page()),
+            backend.max_results(),
+        )
+    };
 
+    // Searches can finish out of order; only the latest one may touch the results.
+    // Starts a new generation and returns a check for whether it is still the latest.
+    let next_generation = move || {
+        search_generation.update_value(|g| *g += 1);
+        let generation = search_generation.get_value();
+        move || search_generation.get_value() == generation
+    };
+
+    // What the results table shows: the current page, or the loaded set filtered and
+    // sorted client-side with the sort controls
+    let displayed = Memo::new(move |_| match loaded.get() {
+        Some(mut repos) => {
+            let text = local_filter.get();
+            repos.retain(|repo| load_all::matches_text(repo, &text));
+            sort_repositories(&mut repos, sort_by.get(), sort_order.get());
+            repos
+        }
+        None => repositories.get(),
+    });
+
+    // `refresh` bypasses the response cache, for when cached results look stale
+    let search_with = move |filters: SearchFilters, refresh: bool| {
+        let backend = backend.get_untracked();
+        let is_current = next_generation();
+
+        set_loaded.set(None);
+        set_load_progress.set(None);
         set_loading.set(true);
         set_error.set(None);
-        set_current_page.set(page);
+        set_search_error.set(None);
+        set_snapshot_used.set(None);
+        // A search this one replaces stops retrying without clearing its countdown
+        set_retry_in.set(None);
+        set_current_page.set(filters.page);
+        let github = selected_forge.0.get_untracked() == Some(Forge::Github);
 
         leptos::task::spawn_local(async move {
-            match search_repositories(filters).await {
+            let result = with_retry(
+                RetryPolicy::default(),
+                || {
+                    if refresh {
+                        backend.refresh(&filters)
+                    } else {
+                        backend.search(&filters)
+                    }
+                },
+                |secs| {
+                    if is_current() {
+                        set_retry_in.set((secs > 0).then_some(secs));
+                    }
+                },
+                is_current,
+            )
+            .await;
+            if !is_current() {
+                return;
+            }
+            match result {
                 Ok(result) => {
                     set_total_count.set(result.response.total_count);
                     set_repositories.set(result.response.items);
-                    set_rate_limit.set(result.rate_limit);
+                    // Responses served from the cache carry no quota headers; keep the last known
+                    if result.rate_limit.is_some() || !result.cached {
+                        set_rate_limit.set(result.rate_limit);
+                    }
+                    set_from_cache.set(result.cached);
                     set_incomplete_results.set(result.response.incomplete_results);
+                    set_warnings.set(result.warnings);
                 }
                 Err(e) => {
-                    set_error.set(Some(e));
+                    // Show the generated snapshot of this search, if there is one, until the
+                    // banner's countdown searches again
+                    let rate_limited = matches!(
+                        e,
+                        SearchError::RateLimited { .. } | SearchError::SecondaryRateLimited { .. }
+                    );
+                    if rate_limited && github {
+                        let snapshot = snapshots::fallback(&filters).await;
+                        if !is_current() {
+                            return;
+                        }
+                        if let Some((snapshot, page)) = snapshot {
+                            set_total_count.set(page.total_count);
+                            set_repositories.set(page.items);
+                            set_from_cache.set(false);
+                            set_incomplete_results.set(false);
+                            set_warnings.set(Vec::new());
+                            set_snapshot_used
+                                .set(Some((snapshot.title, format_date(&snapshot.generated_at))));
+                        }
+                    }
+                    set_search_error.set(Some((e, build_search_query(&filters))));
                 }
             }
             set_loading.set(false);
         });
     };
+    let run_search = move |filters: SearchFilters| search_with(filters, false);
+
+    // Filters as currently set in the controls, which may not have been searched yet
+    let current_filters = move |page: u32| SearchFilters {
+        query: query.get_untracked(),
+        language: language.get_untracked(),
+        min_stars: min_stars.get_untracked(),
+        created_from: created_from.get_untracked(),
+        created_to: created_to.get_untracked(),
+        pushed_from: pushed_from.get_untracked(),
+        pushed_to: pushed_to.get_untracked(),
+        topics: topics.get_untracked(),
+        fork_filter: fork_filter.get_untracked(),
+        archived_filter: archived_filter.get_untracked(),
+        sort_by: sort_by.get_untracked(),
+        sort_order: sort_order.get_untracked(),
+        page,
+        per_page: per_page.get_untracked(),
+    };
+
+    // Pushes filters into the URL; the query-string effect below runs the search
+    let apply_filters = move |filters: SearchFilters| {
+        if filters == SearchFilters::from_query_map(&query_map.get_untracked()) {
+            // Same URL as before (e.g. a repeated search), so the router won't notify us
+            run_search(filters);
+            return;
+        }
+
+        let search = filters.to_query_string();
+        let pathname = location.pathname.get_untracked();
+        let url = if search.is_empty() {
+            pathname
+        } else {
+            format!("{}?{}", pathname, search)
+        };
+        navigate.with_value(|navigate| navigate(&url, Default::default()));
+    };
+
+    let do_search = move |page: u32| apply_filters(current_filters(page));
+
+    // Typing searches on its own once the user pauses; Enter or the button searches at once
+    let pending_query_search = StoredValue::new(Option::<TimeoutHandle>::None);
+    let cancel_query_search = move || {
+        if let Some(handle) = pending_query_search.get_value() {
+            handle.clear();
+        }
+        pending_query_search.set_value(None);
+    };
+    let schedule_query_search = move || {
+        cancel_query_search();
+        if let Ok(handle) = set_timeout_with_handle(
+            move || {
+                pending_query_search.set_value(None);
+                do_search(1);
+            },
+            Duration::from_millis(QUERY_DEBOUNCE_MS),
+        ) {
+            pending_query_search.set_value(Some(handle));
+        }
+    };
+    on_cleanup(cancel_query_search);
+
+    let add_topic = move |topic: String| {
+        let topic = normalize_topic(&topic);
+        if topic.is_empty() || topics.with_untracked(|t| t.contains(&topic)) {
+            return;
+        }
+        set_topics.update(|t| t.push(topic));
+        do_search(1);
+    };
+
+    let remove_topic = move |topic: String| {
+        set_topics.update(|t| t.retain(|existing| *existing != topic));
+        do_search(1);
+    };
 
     let go_to_page = move |page: u32| {
         if page >= 1 && page <= total_pages() && !loading.get() {
@@ -465,6 +1219,11 @@ pub fn App() -> impl IntoView {
         set_query.set(String::new());
         set_language.set("All".to_string());
         set_min_stars.set(String::new());
+        set_created_from.set(String::new());
+        set_created_to.set(String::new());
+        set_pushed_from.set(String::new());
+        set_pushed_to.set(String::new());
+        set_topics.set(Vec::new());
         set_fork_filter.set(ForkFilter::All);
         set_archived_filter.set(ArchivedFilter::ActiveOnly);
         set_sort_by.set(SortBy::Stars);
@@ -472,21 +1231,244 @@ pub fn App() -> impl IntoView {
         do_search(1);
     };
 
-    // Initial search on load
-    {
-        let do_search = do_search.clone();
-        Effect::new(move |_| {
+    // Shows the quota for the current credentials without spending a search request
+    let refresh_rate_limit = move || {
+        let backend = backend.get_untracked();
+        set_error.set(None);
+        leptos::task::spawn_local(async move {
+            match backend.rate_limit().await {
+                Ok(rate_limit) => set_rate_limit.set(rate_limit),
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let save_token = move |_| {
+        let token = token_input.get().trim().to_string();
+        if token.is_empty() {
+            storage::remove_item(TOKEN_STORAGE_KEY);
+        } else {
+            storage::set_item(TOKEN_STORAGE_KEY, &token);
+        }
+        set_token_input.set(token.clone());
+        github_token.0.set(token);
+        refresh_rate_limit();
+    };
+
+    let clear_token = move |_| {
+        storage::remove_item(TOKEN_STORAGE_KEY);
+        set_token_input.set(String::new());
+        github_token.0.set(String::new());
+        refresh_rate_limit();
+    };
+
+    let save_gitlab = move |connection: ForgeConnection| {
+        forge_config.update(|c| c.gitlab = connection);
+        if selected_forge.includes(Forge::Gitlab) {
             do_search(1);
+        }
+    };
+
+    let save_gitea = move |connection: ForgeConnection| {
+        forge_config.update(|c| c.gitea = connection);
+        if selected_forge.includes(Forge::Gitea) {
+            do_search(1);
+        }
+    };
+
+    let save_api_url = move |_| {
+        let input = api_url_input.get_untracked();
+        let url = if input.trim().is_empty() {
+            storage::remove_item(API_URL_STORAGE_KEY);
+            default_api_url()
+        } else {
+            let url = normalize_api_url(&input);
+            storage::set_item(API_URL_STORAGE_KEY, &url);
+            url
+        };
+        set_api_url_input.set(url.clone());
+        api_url.0.set(url);
+        do_search(1);
+    };
+
+    let reset_api_url = move |_| {
+        storage::remove_item(API_URL_STORAGE_KEY);
+        set_api_url_input.set(default_api_url());
+        api_url.0.set(default_api_url());
+        do_search(1);
+    };
+
+    // Infinite scroll: appends the page after the last one shown, leaving the URL alone
+    let append_next_page = move || {
+        let next_page = current_page.get_untracked() + 1;
+        if loading.get_untracked()
+            || next_page > total_pages()
+            || loaded.with_untracked(Option::is_some)
+            || load_progress.with_untracked(Option::is_some)
+        {
+            return;
+        }
+        let filters = SearchFilters {
+            page: next_page,
+            ..SearchFilters::from_query_map(&query_map.get_untracked())
+        };
+        let backend = backend.get_untracked();
+        let is_current = next_generation();
+        set_loading.set(true);
+        set_retry_in.set(None);
+
+        leptos::task::spawn_local(async move {
+            let result = with_retry(
+                RetryPolicy::default(),
+                || backend.search(&filters),
+                |secs| {
+                    if is_current() {
+                        set_retry_in.set((secs > 0).then_some(secs));
+                    }
+                },
+                is_current,
+            )
+            .await;
+            if !is_current() {
+                return;
+            }
+            match result {
+                Ok(result) => {
+                    // Results can shift between requests; don't show any repository twice
+                    set_repositories.update(|repos| {
+                        let mut items = result.response.items;
+                        items.retain(|item| !repos.iter().any(|r| r.id == item.id));
+                        repos.extend(items);
+                    });
+                    set_current_page.set(next_page);
+                    set_total_count.set(result.response.total_count);
+                    if result.rate_limit.is_some() {
+                        set_rate_limit.set(result.rate_limit);
+                    }
+                    set_warnings.update(|w| w.extend(result.warnings));
+                }
+                Err(e) => set_search_error.set(Some((e, build_search_query(&filters)))),
+            }
+            set_loading.set(false);
         });
-    }
+    };
 
-    view! {
-        <div class="app">
-            <header>
-                <h1>"OSS Explorer"</h1>
-                <p class="subtitle">"Discover open source projects by language, creation date, and activity"</p>
-            </header>
+    // Fetches every page of the search currently shown into the local result set
+    let load_all_pages = move |_: web_sys::MouseEvent| {
+        let filters = SearchFilters::from_query_map(&query_map.get_untracked());
+        let backend = backend.get_untracked();
+        let is_current = next_generation();
+        set_error.set(None);
+        set_load_progress.set(Some("Fetching results...".to_string()));
+
+        let slice = slice_by_date.get_untracked() && backend.max_results().is_some();
+
+        leptos::task::spawn_local(async move {
+            let on_progress = |progress: load_all::LoadProgress| {
+                if is_current() {
+                    set_load_progress.set(Some(progress.to_string()));
+                }
+            };
+            let result = if slice {
+                load_all::fetch_sliced_by_date(backend.as_ref(), filters, on_progress, is_current)
+                    .await
+            } else {
+                load_all::fetch_all_pages(backend.as_ref(), filters, on_progress, is_current).await
+            };
+            // Cleared even when superseded, since nothing else takes down the progress
+            set_load_progress.set(None);
+            if !is_current() {
+                return;
+            }
+            match result {
+                Ok((repos, load_warnings)) => {
+                    set_local_filter.set(String::new());
+                    set_loaded.set(Some(repos));
+                    set_warnings.set(load_warnings);
+                }
+                Err(e) => set_error.set(Some(format!("Loading all results failed: {}", e))),
+            }
+        });
+    };
+
+    let do_export = move |_| {
+        let format = export_format.get_untracked();
+
+        // Loaded results are exported as filtered and sorted, without further requests
+        if !export_all.get_untracked() || loaded.with_untracked(Option::is_some) {
+            let content = format.render(&displayed.get_untracked());
+            if let Err(e) = export::download(format, &content) {
+                set_error.set(Some(e));
+            }
+            return;
+        }
+
+        // Export the search currently shown, not any filter edits that haven't been searched yet
+        let filters = SearchFilters::from_query_map(&query_map.get_untracked());
+        let backend = backend.get_untracked();
+        set_export_progress.set(Some("Fetching results...".to_string()));
+
+        leptos::task::spawn_local(async move {
+            let result = load_all::fetch_all_pages(
+                backend.as_ref(),
+                filters,
+                |progress| {
+                    set_export_progress.set(Some(progress.to_string()));
+                },
+                || true,
+            )
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|(repos, _)| export::download(format, &format.render(&repos)));
+
+            if let Err(e) = result {
+                set_error.set(Some(format!("Export failed: {}", e)));
+            }
+            set_export_progress.set(None);
+        });
+    };
+
+    // The URL is the source of truth: on load and on back/forward navigation,
+    // restore the filter controls from the query string and re-run the search
+    Effect::new(move |_| {
+        let filters = SearchFilters::from_query_map(&query_map.get());
+        restore_controls(&filters);
+        // The server searched GitHub; a browser set to another forge still searches its own
+        if skip_first_search.get_value() {
+            skip_first_search.set_value(false);
+            if selected_forge.0.get_untracked() == Some(Forge::Github) {
+                return;
+            }
+        }
+        run_search(filters);
+    });
+
+    // Switching forges re-runs the current search there
+    Effect::new(move |previous: Option<Option<Forge>>| {
+        let forge = selected_forge.0.get();
+        if previous.is_some_and(|p| p != forge) {
+            run_search(SearchFilters::from_query_map(&query_map.get_untracked()));
+        }
+        forge
+    });
 
+    view! {
+        <div class="search-page">
+            <Title text=move || search_title(&SearchFilters::from_query_map(&query_map.get()))/>
+            <Meta
+                property="og:title"
+                content=move || search_title(&SearchFilters::from_query_map(&query_map.get()))
+            />
+            <Meta
+                property="og:description"
+                content=move || format!(
+                    "{} open source repositories found for {}",
+                    format_number(total_count.get()),
+                    build_search_query(&SearchFilters::from_query_map(&query_map.get()))
+                )
+            />
+            <Meta property="og:type" content="website"/>
+            <Meta property="og:site_name" content="OSS Explorer"/>
             <div class="controls">
                 <div class="search-box">
                     <input
@@ -495,14 +1477,22 @@ pub fn App() -> impl IntoView {
                         prop:value=move || query.get()
                         on:input=move |ev| {
                             set_query.set(event_target_value(&ev));
+                            schedule_query_search();
                         }
                         on:keydown=move |ev| {
                             if ev.key() == "Enter" {
+                                cancel_query_search();
                                 do_search(1);
                             }
                         }
                     />
-                    <button on:click=move |_| do_search(1) disabled=move || loading.get()>
+                    <button
+                        on:click=move |_| {
+                            cancel_query_search();
+                            do_search(1);
+                        }
+                        disabled=move || loading.get()
+                    >
                         {move || if loading.get() { "Searching..." } else { "Search" }}
                     </button>
                 </div>
@@ -543,20 +1533,15 @@ pub fn App() -> impl IntoView {
                     <div class="filter-group">
                         <label>"Sort by:"</label>
                         <select on:change=move |ev| {
-                            let value = event_target_value(&ev);
-                            set_sort_by.set(match value.as_str() {
-                                "stars" => SortBy::Stars,
-                                "forks" => SortBy::Forks,
-                                "issues" => SortBy::Issues,
-                                "created" => SortBy::Created,
-                                "updated" => SortBy::Updated,
-                                _ => SortBy::Stars,
-                            });
-                            do_search(1);
+                            set_sort_by.set(SortBy::from_param(&event_target_value(&ev)));
+                            // Loaded results are re-sorted locally instead
+                            if loaded.with_untracked(Option::is_none) {
+                                do_search(1);
+                            }
                         }>
                             <option value="stars" selected=move || sort_by.get() == SortBy::Stars>"Stars"</option>
-                            <option value="forks" selected=move || sort_by.get() == SortBy::Forks>"Forks"</option>
-                            <option value="issues" selected=move || sort_by.get() == SortBy::Issues>"Issues"</option>
+                            <option value="forks" selected=move || sort_by.get() == SortBy::Forks disabled=move || !backend.0.get().supports_sort(SortBy::Forks)>"Forks"</option>
+                            <option value="issues" selected=move || sort_by.get() == SortBy::Issues disabled=move || !backend.0.get().supports_sort(SortBy::Issues)>"Issues"</option>
                             <option value="created" selected=move || sort_by.get() == SortBy::Created>"Created Date"</option>
                             <option value="updated" selected=move || sort_by.get() == SortBy::Updated>"Last Updated"</option>
                         </select>
@@ -565,9 +1550,10 @@ pub fn App() -> impl IntoView {
                     <div class="filter-group">
                         <label>"Order:"</label>
                         <select on:change=move |ev| {
-                            let value = event_target_value(&ev);
-                            set_sort_order.set(if value == "asc" { SortOrder::Asc } else { SortOrder::Desc });
-                            do_search(1);
+                            set_sort_order.set(SortOrder::from_param(&event_target_value(&ev)));
+                            if loaded.with_untracked(Option::is_none) {
+                                do_search(1);
+                            }
                         }>
                             <option value="desc" selected=move || sort_order.get() == SortOrder::Desc>"Descending"</option>
                             <option value="asc" selected=move || sort_order.get() == SortOrder::Asc>"Ascending"</option>
@@ -583,7 +1569,7 @@ pub fn App() -> impl IntoView {
                         }>
                             {PER_PAGE_OPTIONS.iter().map(|&n| {
                                 view! {
-                                    <option value=n.to_string() selected=move || per_page.get() == n>
+                                    <option value=n.to_string() selected=move || per_page.get() == n disabled=move || n > backend.0.get().max_per_page()>
                                         {n.to_string()}
                                     </option>
                                 }
@@ -596,39 +1582,119 @@ pub fn App() -> impl IntoView {
                     <button class="toggle-btn" on:click=move |_| set_show_advanced.update(|v| *v = !*v)>
                         {move || if show_advanced.get() { "Hide Advanced Filters" } else { "Show Advanced Filters" }}
                     </button>
+                    <button class="toggle-btn" on:click=move |_| set_show_saved.update(|v| *v = !*v)>
+                        {move || if show_saved.get() { "Hide Saved Searches" } else { "Saved Searches" }}
+                    </button>
+                    <button class="toggle-btn" on:click=move |_| set_show_settings.update(|v| *v = !*v)>
+                        {move || if show_settings.get() { "Hide Settings" } else { "Settings" }}
+                    </button>
                     <button class="clear-btn" on:click=clear_filters>
                         "Clear All Filters"
                     </button>
                 </div>
 
+                {move || show_saved.get().then(|| view! {
+                    <SavedSearchesPanel current=move || current_filters(1) on_apply=apply_filters/>
+                })}
+
+                {move || show_settings.get().then(|| view! {
+                    <div class="settings-panel">
+                        <div class="filter-group token-group">
+                            <label>"GitHub Token:"</label>
+                            <input
+                                type="password"
+                                placeholder="Personal access token (optional)"
+                                prop:value=move || token_input.get()
+                                on:input=move |ev| {
+                                    set_token_input.set(event_target_value(&ev));
+                                }
+                            />
+                            <button class="toggle-btn" on:click=save_token>"Save Token"</button>
+                            <button class="clear-btn" on:click=clear_token>"Clear Token"</button>
+                        </div>
+                        <p class="settings-hint">
+                            {move || if github_token.0.get().is_empty() {
+                                "Searching anonymously. A token raises the search limit from 10 to 30 requests per minute and is stored only in this browser."
+                            } else {
+                                "Searching with your token. It is stored only in this browser."
+                            }}
+                        </p>
+                        <div class="filter-group token-group">
+                            <label>"API URL:"</label>
+                            <input
+                                type="url"
+                                placeholder="https://github.example.com/api/v3"
+                                prop:value=move || api_url_input.get()
+                                on:input=move |ev| {
+                                    set_api_url_input.set(event_target_value(&ev));
+                                }
+                            />
+                            <button class="toggle-btn" on:click=save_api_url>"Save URL"</button>
+                            <button class="clear-btn" on:click=reset_api_url>"Reset"</button>
+                        </div>
+                        <p class="settings-hint">
+                            "For GitHub Enterprise Server, enter your instance's API URL (a bare host gets /api/v3 added). Tokens are sent to whichever API is configured."
+                        </p>
+                        <ForgeConnectionFields
+                            label="GitLab:"
+                            placeholder="https://gitlab.com"
+                            connection=forge_config.0.with_untracked(|c| c.gitlab.clone())
+                            on_save=save_gitlab
+                        />
+                        <p class="settings-hint">
+                            "GitLab.com or a self-hosted GitLab instance, used when GitLab is selected above."
+                        </p>
+                        <ForgeConnectionFields
+                            label="Gitea / Forgejo:"
+                            placeholder="https://codeberg.org"
+                            connection=forge_config.0.with_untracked(|c| c.gitea.clone())
+                            on_save=save_gitea
+                        />
+                        <p class="settings-hint">
+                            "Codeberg or any Gitea or Forgejo instance, used when Gitea / Forgejo is selected above."
+                        </p>
+                        <label class="settings-checkbox">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || persistent_cache.get()
+                                on:change=move |ev| {
+                                    let enabled = event_target_checked(&ev);
+                                    cache::set_persistent_enabled(enabled);
+                                    set_persistent_cache.set(enabled);
+                                }
+                            />
+                            "Keep cached search results between visits"
+                        </label>
+                        <p class="settings-hint">
+                            "Searches are cached for 5 minutes and then revalidated, which doesn't use up the rate limit when nothing changed. With this on, the cache is also kept in this browser's IndexedDB."
+                        </p>
+                    </div>
+                })}
+
                 {move || show_advanced.get().then(|| view! {
                     <div class="advanced-filters">
                         <div class="filter-group">
                             <label>"Repository Type:"</label>
                             <select on:change=move |ev| {
-                                let value = event_target_value(&ev);
-                                set_fork_filter.set(match value.as_str() {
-                                    "original" => ForkFilter::OriginalOnly,
-                                    "forks" => ForkFilter::ForksOnly,
-                                    _ => ForkFilter::All,
-                                });
+                                set_fork_filter.set(ForkFilter::from_param(&event_target_value(&ev)));
                                 do_search(1);
                             }>
                                 <option value="all" selected=move || fork_filter.get() == ForkFilter::All>"All Repos"</option>
                                 <option value="original" selected=move || fork_filter.get() == ForkFilter::OriginalOnly>"Original Only"</option>
-                                <option value="forks" selected=move || fork_filter.get() == ForkFilter::ForksOnly>"Forks Only"</option>
+                                <option
+                                    value="forks"
+                                    selected=move || fork_filter.get() == ForkFilter::ForksOnly
+                                    disabled=move || !backend.0.get().supports_fork_filter(ForkFilter::ForksOnly)
+                                >
+                                    "Forks Only"
+                                </option>
                             </select>
                         </div>
 
                         <div class="filter-group">
                             <label>"Status:"</label>
                             <select on:change=move |ev| {
-                                let value = event_target_value(&ev);
-                                set_archived_filter.set(match value.as_str() {
-                                    "active" => ArchivedFilter::ActiveOnly,
-                                    "archived" => ArchivedFilter::ArchivedOnly,
-                                    _ => ArchivedFilter::All,
-                                });
+                                set_archived_filter.set(ArchivedFilter::from_param(&event_target_value(&ev)));
                                 do_search(1);
                             }>
                                 <option value="active" selected=move || archived_filter.get() == ArchivedFilter::ActiveOnly>"Active Only"</option>
@@ -636,8 +1702,65 @@ pub fn App() -> impl IntoView {
                                 <option value="archived" selected=move || archived_filter.get() == ArchivedFilter::ArchivedOnly>"Archived Only"</option>
                             </select>
                         </div>
+
+                        <DateRangeFilter
+                            label="Created:"
+                            presets=CREATED_PRESETS
+                            from=created_from
+                            set_from=set_created_from
+                            to=created_to
+                            set_to=set_created_to
+                            on_change=move || do_search(1)
+                        />
+
+                        <DateRangeFilter
+                            label="Last push:"
+                            presets=PUSHED_PRESETS
+                            from=pushed_from
+                            set_from=set_pushed_from
+                            to=pushed_to
+                            set_to=set_pushed_to
+                            on_change=move || do_search(1)
+                        />
+
+                        <div class="filter-group topic-input">
+                            <label>"Topic:"</label>
+                            <input
+                                type="text"
+                                placeholder="e.g. cli, machine-learning"
+                                prop:value=move || topic_input.get()
+                                on:input=move |ev| set_topic_input.set(event_target_value(&ev))
+                                on:keydown=move |ev| {
+                                    if ev.key() == "Enter" {
+                                        add_topic(topic_input.get_untracked());
+                                        set_topic_input.set(String::new());
+                                    }
+                                }
+                            />
+                        </div>
                     </div>
                 })}
+
+                {move || {
+                    let active = topics.get();
+                    (!active.is_empty()).then(|| view! {
+                        <div class="active-topics">
+                            <span class="active-topics-label">"Topics:"</span>
+                            {active.into_iter().map(|topic| {
+                                let remove = topic.clone();
+                                view! {
+                                    <button
+                                        class="topic-chip active"
+                                        title="Remove this topic"
+                                        on:click=move |_| remove_topic(remove.clone())
+                                    >
+                                        {topic}" ×"
+                                    </button>
+                                }
+                            }).collect::<Vec<_>>()}
+                        </div>
+                    })
+                }}
             </div>
 
             // Rate limit indicator
@@ -652,7 +1775,9 @@ pub fn App() -> impl IntoView {
                 };
                 view! {
                     <div class=format!("rate-limit-info {}", status_class)>
-                        <span class="rate-limit-label">"API Rate Limit: "</span>
+                        <span class="rate-limit-label">
+                            {move || if backend.0.get().authenticated() { "API Rate Limit (authenticated): " } else { "API Rate Limit: " }}
+                        </span>
                         <span class="rate-limit-value">{rl.remaining}" / "{rl.limit}</span>
                         {(rl.remaining < 10).then(|| view! {
                             <span class="rate-limit-reset">" (resets at "{format_reset_time(rl.reset_timestamp)}")"</span>
@@ -667,9 +1792,37 @@ pub fn App() -> impl IntoView {
                 </div>
             })}
 
+            {move || retry_in.get().map(|secs| view! {
+                <div class="warning">
+                    <strong>"Search failed: "</strong>{format!("retrying in {}s...", secs)}
+                </div>
+            })}
+
+            {move || search_error.get().map(|(error, query)| view! {
+                <SearchErrorBanner
+                    error=error
+                    query=query
+                    on_retry=move || run_search(SearchFilters::from_query_map(&query_map.get_untracked()))
+                    on_open_settings=move || set_show_settings.set(true)
+                />
+            })}
+
+            {move || snapshot_used.get().map(|(title, date)| view! {
+                <div class="warning">
+                    <strong>"Showing a snapshot: "</strong>
+                    {format!("\"{}\" as of {}, while live search is unavailable.", title, date)}
+                </div>
+            })}
+
+            {move || warnings.get().into_iter().map(|w| view! {
+                <div class="warning">
+                    <strong>"Partial results: "</strong>{w}
+                </div>
+            }).collect::<Vec<_>>()}
+
             {move || incomplete_results.get().then(|| view! {
                 <div class="warning">
-                    <strong>"Warning: "</strong>"Results may be incomplete due to GitHub API timeout. Try a more specific search."
+                    <strong>"Warning: "</strong>{move || format!("Results may be incomplete due to {} API timeout. Try a more specific search.", backend.0.get().name())}
                 </div>
             })}
 
@@ -677,23 +1830,127 @@ pub fn App() -> impl IntoView {
                 <span class="count">
                     {move || {
                         let total = total_count.get();
-                        if total > 1000 {
-                            format!("{} repositories found (showing first 1,000)", format_number(total))
-                        } else {
-                            format!("{} repositories found", format_number(total))
+                        if let Some(all) = loaded.get() {
+                            return format!(
+                                "Showing {} of {} loaded repositories",
+                                format_number(displayed.with(Vec::len) as u32),
+                                format_number(all.len() as u32)
+                            );
+                        }
+                        match backend.0.get().max_results() {
+                            Some(max) if total > max => format!(
+                                "{} repositories found (showing first {})",
+                                format_number(total),
+                                max
+                            ),
+                            _ => format!("{} repositories found", format_number(total)),
                         }
                     }}
+                    {move || from_cache.get().then(|| view! {
+                        <span class="cached-note">" (cached)"</span>
+                    })}
                 </span>
-                <span class="page-info">
-                    {move || format!("Page {} of {}", current_page.get(), total_pages().max(1))}
-                </span>
+                <button
+                    class="toggle-btn"
+                    title="Search again, skipping cached results"
+                    disabled=move || loading.get()
+                    on:click=move |_| search_with(SearchFilters::from_query_map(&query_map.get_untracked()), true)
+                >
+                    "Refresh"
+                </button>
+                {move || match (loaded.with(Option::is_some), load_progress.get()) {
+                    (_, Some(progress)) => view! {
+                        <span class="load-progress">{progress}</span>
+                    }.into_any(),
+                    (true, None) => view! {
+                        <div class="local-controls">
+                            <input
+                                type="text"
+                                placeholder="Filter loaded results"
+                                prop:value=move || local_filter.get()
+                                on:input=move |ev| set_local_filter.set(event_target_value(&ev))
+                            />
+                            <button class="clear-btn" on:click=move |_| set_loaded.set(None)>"Back to pages"</button>
+                        </div>
+                    }.into_any(),
+                    (false, None) => view! {
+                        <div class="local-controls">
+                            <button
+                                class="toggle-btn"
+                                title="Fetch every page to sort, filter and export them locally"
+                                disabled=move || loading.get() || total_pages() <= 1
+                                on:click=load_all_pages
+                            >
+                                "Load all"
+                            </button>
+                            {move || backend.0.get().max_results().map(|max| view! {
+                                <label
+                                    class="inline-option"
+                                    title=format!("Search one creation date range at a time, splitting ranges with more than {} results, to fetch the complete result set", format_number(max))
+                                >
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || slice_by_date.get()
+                                        on:change=move |ev| set_slice_by_date.set(event_target_checked(&ev))
+                                    />
+                                    {format!("Past the first {}", format_number(max))}
+                                </label>
+                            })}
+                        </div>
+                    }.into_any(),
+                }}
+                <div class="export-controls">
+                    <select on:change=move |ev| set_export_format.set(ExportFormat::from_param(&event_target_value(&ev)))>
+                        <option value="csv" selected=move || export_format.get() == ExportFormat::Csv>"CSV"</option>
+                        <option value="json" selected=move || export_format.get() == ExportFormat::Json>"JSON"</option>
+                        <option value="md" selected=move || export_format.get() == ExportFormat::Markdown>"Markdown"</option>
+                    </select>
+                    <select on:change=move |ev| set_export_all.set(event_target_value(&ev) == "all")>
+                        <option value="page" selected=move || !export_all.get()>"This page"</option>
+                        <option value="all" selected=move || export_all.get()>
+                            {move || match backend.0.get().max_results() {
+                                Some(max) => format!("All results (up to {})", max),
+                                None => "All results".to_string(),
+                            }}
+                        </option>
+                    </select>
+                    <button
+                        class="toggle-btn"
+                        on:click=do_export
+                        disabled=move || export_progress.get().is_some() || displayed.with(Vec::is_empty)
+                    >
+                        {move || export_progress.get().unwrap_or_else(|| "Export".to_string())}
+                    </button>
+                </div>
+                {move || loaded.with(Option::is_none).then(|| view! {
+                    <span class="page-info">
+                        {move || if infinite_scroll.get() {
+                            format!("Pages 1-{} of {}", current_page.get(), total_pages().max(1))
+                        } else {
+                            format!("Page {} of {}", current_page.get(), total_pages().max(1))
+                        }}
+                    </span>
+                })}
+                <label class="inline-option" title="Load the next page automatically when scrolling to the end of the results">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || infinite_scroll.get()
+                        on:change=move |ev| {
+                            // The results already shown stay as they are in either mode
+                            let enabled = event_target_checked(&ev);
+                            infinite_scroll::save_preference(enabled);
+                            set_infinite_scroll.set(enabled);
+                        }
+                    />
+                    "Infinite scroll"
+                </label>
             </div>
 
             <div class="results">
                 {move || {
-                    if loading.get() && repositories.get().is_empty() {
+                    if loading.get() && displayed.with(Vec::is_empty) {
                         view! { <div class="loading">"Loading repositories..."</div> }.into_any()
-                    } else if repositories.get().is_empty() {
+                    } else if displayed.with(Vec::is_empty) {
                         view! { <div class="empty">"No repositories found. Try a different search."</div> }.into_any()
                     } else {
                         view! {
@@ -709,9 +1966,16 @@ pub fn App() -> impl IntoView {
                                     </tr>
                                 </thead>
                                 <tbody>
-                                    {move || repositories.get().into_iter().map(|repo| {
+                                    {move || displayed.get().into_iter().map(|repo| {
                                         let repo_url = repo.html_url.clone();
                                         let repo_name = repo.full_name.clone();
+                                        let detail_url = repo_detail_url(&repo);
+                                        let forge_label = repo.forge.map(|f| f.label());
+                                        let open_title = format!(
+                                            "Open on {}",
+                                            forge_label.map_or_else(|| backend.get_untracked().name().to_string(), str::to_string)
+                                        );
+                                        let watch_repo = repo.clone();
                                         let description = repo.description.clone().unwrap_or_default();
                                         let language = repo.language.clone().unwrap_or_else(|| "Unknown".to_string());
                                         let stars = format_number(repo.stargazers_count);
@@ -722,6 +1986,7 @@ pub fn App() -> impl IntoView {
                                         let fallback_avatar = DEFAULT_AVATAR.to_string();
                                         let is_fork = repo.fork;
                                         let is_archived = repo.archived;
+                                        let repo_topics = repo.topics.clone().unwrap_or_default();
 
                                         view! {
                                             <tr class:archived=is_archived class:forked=is_fork>
@@ -743,13 +2008,38 @@ pub fn App() -> impl IntoView {
                                                         />
                                                         <div class="repo-details">
                                                             <div class="repo-name-row">
-                                                                <a href=repo_url target="_blank" class="repo-name">
+                                                                <A href=detail_url attr:class="repo-name">
                                                                     {repo_name}
+                                                                </A>
+                                                                <WatchToggle repo=watch_repo/>
+                                                                <a href=repo_url target="_blank" class="external-link" title=open_title>
+                                                                    "↗"
                                                                 </a>
+                                                                {forge_label.filter(|_| selected_forge.0.get_untracked().is_none()).map(|label| view! {
+                                                                    <span class="badge forge-badge">{label}</span>
+                                                                })}
                                                                 {is_fork.then(|| view! { <span class="badge fork-badge">"Fork"</span> })}
                                                                 {is_archived.then(|| view! { <span class="badge archived-badge">"Archived"</span> })}
                                                             </div>
                                                             <p class="repo-description">{description}</p>
+                                                            {(!repo_topics.is_empty()).then(|| view! {
+                                                                <div class="topic-chips">
+                                                                    {repo_topics.into_iter().map(|topic| {
+                                                                        let is_active = topic.clone();
+                                                                        let add = topic.clone();
+                                                                        view! {
+                                                                            <button
+                                                                                class="topic-chip"
+                                                                                class:active=move || topics.with(|t| t.contains(&is_active))
+                                                                                title="Filter by this topic"
+                                                                                on:click=move |_| add_topic(add.clone())
+                                                                            >
+                                                                                {topic}
+                                                                            </button>
+                                                                        }
+                                                                    }).collect::<Vec<_>>()}
+                                                                </div>
+                                                            })}
                                                         </div>
                                                     </div>
                                                 </td>
@@ -768,8 +2058,20 @@ pub fn App() -> impl IntoView {
                 }}
             </div>
 
+            // Infinite scroll: the next page is appended when the end of the results comes into view,
+            // except while Load all is fetching or showing its own result set
+            {move || (infinite_scroll.get() && loaded.with(Option::is_none) && load_progress.with(Option::is_none)).then(|| view! {
+                <ScrollSentinel
+                    on_visible=append_next_page
+                    results=Signal::derive(move || repositories.with(Vec::len))
+                />
+                {move || (loading.get() && !repositories.with(Vec::is_empty)).then(|| view! {
+                    <div class="loading">"Loading more repositories..."</div>
+                })}
+            })}
+
             // Pagination controls
-            {move || (total_pages() > 1).then(|| {
+            {move || (total_pages() > 1 && !infinite_scroll.get() && loaded.with(Option::is_none)).then(|| {
                 let page = current_page.get();
                 let pages = total_pages();
                 view! {
@@ -831,9 +2133,6 @@ pub fn App() -> impl IntoView {
                 }
             })}
 
-            <footer>
-                <p>"Powered by the GitHub API | Built with Rust + Leptos"</p>
-            </footer>
         </div>
     }
 }
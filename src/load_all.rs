@@ -0,0 +1,287 @@
+// Loading every page of a search into one local result set
+
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::NaiveDate;
+use gloo_timers::future::TimeoutFuture;
+
+use crate::backend::SearchBackend;
+use crate::error::SearchError;
+use crate::retry::{with_retry, RetryPolicy};
+use crate::{calculate_total_pages, RateLimitInfo, Repository, SearchFilters, SearchResult};
+
+// GitHub's oldest repositories date from late 2007; date slicing starts here when no
+// creation date is set
+const EARLIEST_CREATED: (i32, u32, u32) = (2007, 10, 1);
+
+/// Most results Load all fetches from a forge without a search cap of its own, so a broad
+/// search can't keep fetching (and holding every repository in the page) without end.
+/// Hitting it is reported with the results.
+pub const LOAD_ALL_LIMIT: u32 = 10_000;
+
+// The forge's own cap, or Load all's where the forge has none
+fn load_cap(backend: &dyn SearchBackend) -> u32 {
+    backend.max_results().unwrap_or(LOAD_ALL_LIMIT)
+}
+
+#[derive(Clone, Debug)]
+pub enum LoadProgress {
+    Fetched {
+        pages: u32,
+        total_pages: u32,
+    },
+    /// Slicing by creation date: searching one window, with the results gathered so far
+    Window {
+        from: NaiveDate,
+        to: NaiveDate,
+        results: usize,
+    },
+    /// The quota ran out mid-load; fetching resumes after this many seconds
+    WaitingForQuota {
+        seconds: u64,
+    },
+}
+
+impl fmt::Display for LoadProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadProgress::Fetched { pages, total_pages } => {
+                write!(f, "Fetched page {} of {}...", pages, total_pages)
+            }
+            LoadProgress::Window { from, to, results } => write!(
+                f,
+                "Searching created:{}..{} ({} results so far)...",
+                from, to, results
+            ),
+            LoadProgress::WaitingForQuota { seconds } => {
+                write!(f, "Rate limit reached, resuming in {}s...", seconds)
+            }
+        }
+    }
+}
+
+/// Runs the searches of one load, pausing for the quota to reset when a response says
+/// it is used up instead of failing on the next request. Once `wanted` returns false
+/// (e.g. a newer search replaced the one being loaded) it stops searching and waiting.
+struct Loader<'a, P, W> {
+    backend: &'a dyn SearchBackend,
+    on_progress: P,
+    wanted: W,
+    rate_limit: Option<RateLimitInfo>,
+}
+
+impl<'a, P: Fn(LoadProgress), W: Fn() -> bool + Copy> Loader<'a, P, W> {
+    fn new(backend: &'a dyn SearchBackend, on_progress: P, wanted: W) -> Self {
+        Loader {
+            backend,
+            on_progress,
+            wanted,
+            rate_limit: None,
+        }
+    }
+
+    /// The search's result, or None once the load is no longer wanted
+    async fn search(
+        &mut self,
+        filters: &SearchFilters,
+    ) -> Result<Option<SearchResult>, SearchError> {
+        if !(self.wanted)() {
+            return Ok(None);
+        }
+        if let Some(rate_limit) = self.rate_limit.as_ref().filter(|r| r.remaining == 0) {
+            let now = (js_sys::Date::now() / 1000.0) as u64;
+            let mut seconds = rate_limit.reset_timestamp.saturating_sub(now) + 1;
+            while seconds > 0 {
+                if !(self.wanted)() {
+                    return Ok(None);
+                }
+                (self.on_progress)(LoadProgress::WaitingForQuota { seconds });
+                TimeoutFuture::new(1_000).await;
+                seconds -= 1;
+            }
+        }
+
+        let result = with_retry(
+            RetryPolicy::default(),
+            || self.backend.search(filters),
+            |_| {},
+            self.wanted,
+        )
+        .await?;
+        self.rate_limit = result.rate_limit.clone();
+        Ok(Some(result))
+    }
+
+    /// Fetches the pages after `first` at the filters' page size, up to the forge's result cap.
+    /// After each page, `progress` turns (pages fetched, total pages, results) into the progress to report.
+    /// A load that is no longer wanted returns the pages fetched so far.
+    async fn rest_of_pages(
+        &mut self,
+        filters: &SearchFilters,
+        first: SearchResult,
+        progress: impl Fn(u32, u32, usize) -> LoadProgress,
+    ) -> Result<Vec<Repository>, SearchError> {
+        let total_pages = calculate_total_pages(
+            first.response.total_count,
+            filters.per_page,
+            Some(load_cap(self.backend)),
+        )
+        .max(1);
+        let mut repos = first.response.items;
+        (self.on_progress)(progress(1, total_pages, repos.len()));
+
+        for page in 2..=total_pages {
+            let page_filters = SearchFilters {
+                page,
+                ..filters.clone()
+            };
+            let Some(result) = self.search(&page_filters).await? else {
+                break;
+            };
+            // Pages aren't checked for being short: forges that filter client-side return short pages mid-search
+            repos.extend(result.response.items);
+            (self.on_progress)(progress(page, total_pages, repos.len()));
+        }
+        Ok(repos)
+    }
+}
+
+/// Fetches every page of a search in order, up to the forge's result cap (or `LOAD_ALL_LIMIT`),
+/// until `wanted` returns false. Returns the results with a warning if the limit cut them short.
+pub async fn fetch_all_pages(
+    backend: &dyn SearchBackend,
+    filters: SearchFilters,
+    on_progress: impl Fn(LoadProgress),
+    wanted: impl Fn() -> bool + Copy,
+) -> Result<(Vec<Repository>, Vec<String>), SearchError> {
+    // The largest pages the forge returns, so the fewest requests are spent
+    let filters = SearchFilters {
+        page: 1,
+        per_page: backend.max_per_page(),
+        ..filters
+    };
+    let mut loader = Loader::new(backend, on_progress, wanted);
+    let Some(first) = loader.search(&filters).await? else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    // A forge's own cap is its search's, and shown with the total; Load all's is only known here
+    let total = first.response.total_count;
+    let mut warnings = Vec::new();
+    if backend.max_results().is_none() && total > LOAD_ALL_LIMIT {
+        warnings.push(format!(
+            "Only the first {} of {} results were loaded. Narrow the search to load the rest.",
+            LOAD_ALL_LIMIT, total
+        ));
+    }
+    let repos = loader
+        .rest_of_pages(&filters, first, |pages, total_pages, _| {
+            LoadProgress::Fetched { pages, total_pages }
+        })
+        .await?;
+    Ok((repos, warnings))
+}
+
+/// Fetches a search's complete result set past the forge's result cap by splitting it into
+/// `created:` date windows, halving each window until its results fit under the cap.
+/// Returns the results with a warning for any single day that still had too many.
+/// Like `fetch_all_pages`, no more windows are searched once `wanted` returns false.
+pub async fn fetch_sliced_by_date(
+    backend: &dyn SearchBackend,
+    filters: SearchFilters,
+    on_progress: impl Fn(LoadProgress),
+    wanted: impl Fn() -> bool + Copy,
+) -> Result<(Vec<Repository>, Vec<String>), SearchError> {
+    let cap = load_cap(backend);
+    let today = chrono::Utc::now().date_naive();
+    let (year, month, day) = EARLIEST_CREATED;
+    let earliest = NaiveDate::from_ymd_opt(year, month, day).unwrap_or(today);
+    let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let from = parse(&filters.created_from).unwrap_or(earliest);
+    let to = parse(&filters.created_to).unwrap_or(today);
+
+    let mut filters = SearchFilters {
+        page: 1,
+        per_page: backend.max_per_page(),
+        ..filters
+    };
+    // A date range would replace the default `stars:>100` of an otherwise empty search
+    if filters.query.is_empty()
+        && filters.min_stars.is_empty()
+        && filters.topics.is_empty()
+        && filters.created_from.is_empty()
+        && filters.created_to.is_empty()
+        && filters.pushed_from.is_empty()
+        && filters.pushed_to.is_empty()
+    {
+        filters.min_stars = ">100".to_string();
+    }
+
+    let mut loader = Loader::new(backend, on_progress, wanted);
+    let mut repos: Vec<Repository> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut warnings = Vec::new();
+    // Oldest window first, so results come out in creation order
+    let mut windows = vec![(from, to)];
+
+    while let Some((from, to)) = windows.pop() {
+        (loader.on_progress)(LoadProgress::Window {
+            from,
+            to,
+            results: repos.len(),
+        });
+        let window_filters = SearchFilters {
+            created_from: from.format("%Y-%m-%d").to_string(),
+            created_to: to.format("%Y-%m-%d").to_string(),
+            ..filters.clone()
+        };
+        let Some(first) = loader.search(&window_filters).await? else {
+            break;
+        };
+
+        let total = first.response.total_count;
+        if total > cap && from < to {
+            let mid = from + chrono::Duration::days((to - from).num_days() / 2);
+            windows.push((mid.succ_opt().unwrap_or(to), to));
+            windows.push((from, mid));
+            continue;
+        }
+        if total > cap {
+            warnings.push(format!(
+                "{} repositories were created on {}; only the first {} could be fetched.",
+                total, from, cap
+            ));
+        }
+
+        let results = repos.len();
+        let window = loader
+            .rest_of_pages(&window_filters, first, |_, _, fetched| {
+                LoadProgress::Window {
+                    from,
+                    to,
+                    results: results + fetched,
+                }
+            })
+            .await?;
+        // Windows don't overlap, but results can shift between pages while paging
+        repos.extend(window.into_iter().filter(|repo| seen.insert(repo.id)));
+    }
+
+    Ok((repos, warnings))
+}
+
+/// Whether a loaded repository matches the local filter text, by name, description,
+/// language or topic
+pub fn matches_text(repo: &Repository, text: &str) -> bool {
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        return true;
+    }
+    let contains = |value: &str| value.to_lowercase().contains(&text);
+
+    contains(&repo.full_name)
+        || repo.description.as_deref().is_some_and(contains)
+        || repo.language.as_deref().is_some_and(contains)
+        || repo.topics.iter().flatten().any(|topic| contains(topic))
+}
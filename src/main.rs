@@ -1,6 +1,16 @@
-use oss_explorer::App;
-
+#[cfg(target_arch = "wasm32")]
 fn main() {
     console_error_panic_hook::set_once();
-    leptos::mount::mount_to_body(App);
+    // The hydrate bundle takes over pages `oss-explorer serve` rendered; see README
+    #[cfg(feature = "hydrate")]
+    oss_explorer::hydrate();
+    #[cfg(not(feature = "hydrate"))]
+    leptos::mount::mount_to_body(oss_explorer::App);
+}
+
+// Natively the same binary is a command-line tool; see `oss-explorer --help`
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> std::process::ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    oss_explorer::cli::run(&args)
 }
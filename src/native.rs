@@ -0,0 +1,55 @@
+// Blocking GitHub search for native builds (the command line), where reqwasm isn't available
+
+use crate::backend::github::{rate_limit_from_headers, search_result, search_url, JSON_ACCEPT};
+use crate::error::{error_from_parts, SearchError};
+use crate::{SearchFilters, SearchResult};
+
+/// Runs one page of a GitHub repository search against `api_url`
+pub fn search(
+    api_url: &str,
+    token: Option<&str>,
+    filters: &SearchFilters,
+) -> Result<SearchResult, SearchError> {
+    let mut request = ureq::get(&search_url(api_url, filters))
+        .set("Accept", JSON_ACCEPT)
+        .set("User-Agent", "oss-explorer");
+    if let Some(token) = token {
+        request = request.set("Authorization", &format!("Bearer {}", token));
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(status, response)) => {
+            let headers: Vec<(String, String)> = response
+                .headers_names()
+                .into_iter()
+                .filter_map(|name| {
+                    let value = response.header(&name)?.to_string();
+                    Some((name, value))
+                })
+                .collect();
+            let body = response.into_string().unwrap_or_default();
+            let header = |name: &str| {
+                headers
+                    .iter()
+                    .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                    .map(|(_, v)| v.clone())
+            };
+            return Err(error_from_parts(status, header, &body));
+        }
+        Err(e) => return Err(SearchError::Network(e.to_string())),
+    };
+
+    let rate_limit = rate_limit_from_headers(|name| response.header(name).map(str::to_string));
+    let body = response
+        .into_string()
+        .map_err(|e| SearchError::Network(e.to_string()))?;
+    search_result(&body, rate_limit, false)
+}
+
+/// Loads a recorded search response (the API's JSON, as saved with `curl`) in place of a search
+pub fn search_fixture(path: &str) -> Result<SearchResult, SearchError> {
+    let body = std::fs::read_to_string(path)
+        .map_err(|e| SearchError::Network(format!("{}: {}", path, e)))?;
+    search_result(&body, None, false)
+}
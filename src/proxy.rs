@@ -0,0 +1,375 @@
+// `oss-explorer serve`: hosts the built web app and proxies its GitHub searches, so a whole team
+// shares one token's rate limit, one response cache and one request per distinct search
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::backend::github::JSON_ACCEPT;
+#[cfg(feature = "ssr")]
+use crate::backend::github::{search_result, search_url};
+use crate::cli::ServeArgs;
+#[cfg(feature = "ssr")]
+use crate::{ssr, InitialSearch};
+
+// Same freshness window as the browser cache: younger responses are served without asking
+// GitHub, older ones are revalidated with their ETag (a 304 doesn't count against the limit)
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+// Oldest entries are dropped beyond this many
+const MAX_CACHE_ENTRIES: usize = 1000;
+
+// Upstream headers relayed to the browser
+const RELAYED_HEADERS: &[&str] = &[
+    "etag",
+    "retry-after",
+    "x-ratelimit-limit",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+];
+
+/// An upstream answer, as relayed to every request waiting on it
+#[derive(Clone, Debug)]
+struct Upstream {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Upstream {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// A response the proxy makes up itself, e.g. when GitHub can't be reached
+    fn error(status: u16, message: &str) -> Self {
+        Upstream {
+            status,
+            headers: Vec::new(),
+            body: serde_json::json!({ "message": message }).to_string(),
+        }
+    }
+}
+
+struct CachedSearch {
+    response: Upstream,
+    fetched_at: Instant,
+}
+
+/// A search being fetched; requests for the same URL wait on it instead of repeating it
+#[derive(Default)]
+struct InFlight {
+    response: Mutex<Option<Upstream>>,
+    done: Condvar,
+}
+
+struct Proxy {
+    api_url: String,
+    token: Option<String>,
+    dist: PathBuf,
+    cache: Mutex<HashMap<String, CachedSearch>>,
+    in_flight: Mutex<HashMap<String, Arc<InFlight>>>,
+}
+
+/// Serves until the process is stopped; only fails to start
+pub fn run(serve: ServeArgs) -> ExitCode {
+    let server = match Server::http(&serve.listen) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("error: can't listen on {}: {}", serve.listen, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if !Path::new(&serve.dist).join("index.html").is_file() {
+        eprintln!(
+            "warning: {}/index.html not found; build the app with `trunk build --release`",
+            serve.dist
+        );
+    }
+    eprintln!(
+        "Serving {} on http://{}, proxying searches to {} ({})",
+        serve.dist,
+        serve.listen,
+        serve.api_url,
+        if serve.token.is_some() {
+            "with a token"
+        } else {
+            "anonymously"
+        }
+    );
+
+    let proxy = Arc::new(Proxy {
+        api_url: serve.api_url,
+        token: serve.token,
+        dist: PathBuf::from(serve.dist),
+        cache: Mutex::new(HashMap::new()),
+        in_flight: Mutex::new(HashMap::new()),
+    });
+    for request in server.incoming_requests() {
+        let proxy = proxy.clone();
+        thread::spawn(move || proxy.handle(request));
+    }
+    ExitCode::SUCCESS
+}
+
+impl Proxy {
+    fn handle(&self, request: Request) {
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+        // The CLI and the API URL setting add `/api/v3` to a bare host, so accept that prefix too
+        let api_path = path.strip_prefix("/api/v3").unwrap_or(path);
+
+        let result = match (request.method(), api_path) {
+            (Method::Options, "/search/repositories" | "/rate_limit") => {
+                request.respond(with_cors(Response::empty(204)))
+            }
+            (Method::Get, "/search/repositories") => {
+                let upstream_url = format!("{}/search/repositories?{}", self.api_url, query);
+                let response = self.search(&upstream_url);
+                let if_none_match = request_header(&request, "if-none-match");
+                request.respond(api_response(&response, if_none_match.as_deref()))
+            }
+            (Method::Get, "/rate_limit") => {
+                // Free to ask, and should be current, so neither cached nor coalesced
+                let response = self.fetch(&format!("{}/rate_limit", self.api_url), None);
+                request.respond(api_response(&response, None))
+            }
+            (Method::Get | Method::Head, _) => {
+                let response = self.static_file(path, &url);
+                request.respond(response)
+            }
+            _ => request.respond(Response::from_string("Method not allowed").with_status_code(405)),
+        };
+        if let Err(e) = result {
+            eprintln!("error: couldn't send response for {}: {}", url, e);
+        }
+    }
+
+    /// One search, from the cache when fresh, otherwise fetched once however many ask at a time
+    fn search(&self, url: &str) -> Upstream {
+        if let Some(cached) = self.cache.lock().unwrap().get(url) {
+            if cached.fetched_at.elapsed() < CACHE_TTL {
+                return cached.response.clone();
+            }
+        }
+
+        let (in_flight, leader) = {
+            let mut pending = self.in_flight.lock().unwrap();
+            match pending.get(url) {
+                Some(in_flight) => (in_flight.clone(), false),
+                None => {
+                    let in_flight = Arc::new(InFlight::default());
+                    pending.insert(url.to_string(), in_flight.clone());
+                    (in_flight, true)
+                }
+            }
+        };
+
+        if !leader {
+            let mut response = in_flight.response.lock().unwrap();
+            while response.is_none() {
+                response = in_flight.done.wait(response).unwrap();
+            }
+            return response
+                .clone()
+                .unwrap_or_else(|| Upstream::error(502, "no response"));
+        }
+
+        let response = self.revalidate(url);
+        *in_flight.response.lock().unwrap() = Some(response.clone());
+        in_flight.done.notify_all();
+        self.in_flight.lock().unwrap().remove(url);
+        response
+    }
+
+    /// Fetches a search, sending the stale entry's ETag so an unchanged result costs nothing
+    fn revalidate(&self, url: &str) -> Upstream {
+        let stale = self
+            .cache
+            .lock()
+            .unwrap()
+            .get(url)
+            .map(|cached| cached.response.clone());
+        let etag = stale
+            .as_ref()
+            .and_then(|response| response.header("etag"))
+            .map(str::to_string);
+
+        let fresh = self.fetch(url, etag.as_deref());
+        let response = match (fresh.status, stale) {
+            (304, Some(stale)) => stale,
+            (200, _) => fresh,
+            _ => return fresh,
+        };
+
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= MAX_CACHE_ENTRIES && !cache.contains_key(url) {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.fetched_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+        cache.insert(
+            url.to_string(),
+            CachedSearch {
+                response: response.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        response
+    }
+
+    fn fetch(&self, url: &str, etag: Option<&str>) -> Upstream {
+        let mut request = ureq::get(url)
+            .set("Accept", JSON_ACCEPT)
+            .set("User-Agent", "oss-explorer");
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", &format!("Bearer {}", token));
+        }
+        if let Some(etag) = etag {
+            request = request.set("If-None-Match", etag);
+        }
+
+        let response = match request.call() {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => return Upstream::error(502, &format!("GitHub unreachable: {}", e)),
+        };
+        let status = response.status();
+        let headers = RELAYED_HEADERS
+            .iter()
+            .filter_map(|&name| Some((name.to_string(), response.header(name)?.to_string())))
+            .collect();
+        let body = response.into_string().unwrap_or_default();
+        Upstream {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// A file from the built app; any other path is a client-side route and gets the app page
+    fn static_file(&self, path: &str, url: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+        let relative = Path::new(path.trim_start_matches('/'));
+        // Only plain names, never `..` or an absolute path out of the dist directory
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Response::from_string("Not found").with_status_code(404);
+        }
+
+        let mut file = self.dist.join(relative);
+        // Directories serve their index.html, e.g. the generated /top/<slug>/ pages
+        if file.is_dir() && !relative.as_os_str().is_empty() {
+            file = file.join("index.html");
+        }
+        if file.is_file() {
+            return match std::fs::read(&file) {
+                Ok(contents) => Response::from_data(contents)
+                    .with_header(header("Content-Type", content_type(&file))),
+                Err(_) => Response::from_string("Not found").with_status_code(404),
+            };
+        }
+        match std::fs::read_to_string(self.dist.join("index.html")) {
+            Ok(shell) => Response::from_string(self.app_page(shell, url))
+                .with_header(header("Content-Type", "text/html; charset=utf-8")),
+            Err(_) => Response::from_string("Not found").with_status_code(404),
+        }
+    }
+
+    /// index.html as built, for the browser to render
+    #[cfg(not(feature = "ssr"))]
+    fn app_page(&self, shell: String, _url: &str) -> String {
+        shell
+    }
+
+    /// index.html with the page rendered into it; the search page's results come through the
+    /// shared cache, and if that search fails the browser simply runs it again
+    #[cfg(feature = "ssr")]
+    fn app_page(&self, shell: String, url: &str) -> String {
+        let initial = ssr::search_filters_for(url).and_then(|filters| {
+            let response = self.search(&search_url(&self.api_url, &filters));
+            if response.status != 200 {
+                return None;
+            }
+            let result = search_result(&response.body, None, false).ok()?;
+            Some(InitialSearch {
+                filters,
+                response: result.response,
+            })
+        });
+        ssr::render_page(&shell, url, initial)
+    }
+}
+
+fn request_header(request: &Request, name: &str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+        .map(|h| h.value.to_string())
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+/// Lets an app hosted elsewhere use the proxy and read the headers it relies on
+fn with_cors<R: std::io::Read>(response: Response<R>) -> Response<R> {
+    response
+        .with_header(header("Access-Control-Allow-Origin", "*"))
+        .with_header(header(
+            "Access-Control-Allow-Headers",
+            "Accept, Authorization, If-None-Match",
+        ))
+        .with_header(header(
+            "Access-Control-Expose-Headers",
+            &RELAYED_HEADERS.join(", "),
+        ))
+}
+
+/// Relays an upstream answer, or a 304 when the browser already has this version
+fn api_response(
+    upstream: &Upstream,
+    if_none_match: Option<&str>,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let not_modified = upstream.status == 200
+        && if_none_match.is_some()
+        && upstream.header("etag") == if_none_match;
+    let mut response = if not_modified {
+        Response::from_data(Vec::new()).with_status_code(304)
+    } else {
+        Response::from_string(upstream.body.clone())
+            .with_status_code(upstream.status)
+            .with_header(header("Content-Type", "application/json; charset=utf-8"))
+    };
+    for (name, value) in &upstream.headers {
+        response.add_header(header(name, value));
+    }
+    with_cors(response)
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("wasm") => "application/wasm",
+        Some("css") => "text/css; charset=utf-8",
+        Some("json") => "application/json; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("ico") => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,98 @@
+// Retrying searches that failed for transient reasons
+
+use std::future::Future;
+
+use gloo_timers::future::TimeoutFuture;
+
+use crate::error::SearchError;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubled for each retry after that
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// Longest `retry-after` worth waiting out here; longer waits are left to the error banner
+    pub max_retry_after_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            base_delay_ms: 1_000,
+            max_delay_ms: 30_000,
+            max_retry_after_secs: 120,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait after `error` on the given (1-based) attempt, or None to give up
+    pub fn delay_ms(&self, error: &SearchError, attempt: u32) -> Option<u64> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        match error {
+            SearchError::SecondaryRateLimited { retry_after }
+                if *retry_after <= self.max_retry_after_secs =>
+            {
+                Some(retry_after * 1_000)
+            }
+            SearchError::Network(_) => Some(self.backoff_ms(attempt)),
+            SearchError::Http(status) if *status >= 500 => Some(self.backoff_ms(attempt)),
+            _ => None,
+        }
+    }
+
+    /// Exponential backoff with full jitter, so clients that failed together don't retry together
+    fn backoff_ms(&self, attempt: u32) -> u64 {
+        let ceiling = self
+            .base_delay_ms
+            .saturating_mul(1 << (attempt - 1).min(16))
+            .min(self.max_delay_ms);
+        (js_sys::Math::random() * ceiling as f64) as u64 + 1
+    }
+}
+
+/// Runs `attempt` until it succeeds or the policy gives up. While waiting to retry,
+/// `on_wait` is called once a second with the seconds left, then with 0 when retrying.
+/// Once `wanted` returns false (e.g. a newer search replaced this one) no more attempts are
+/// made, so nobody's quota goes on results no one will see; the last error is returned.
+pub async fn with_retry<T, F, Fut>(
+    policy: RetryPolicy,
+    mut attempt: F,
+    on_wait: impl Fn(u64),
+    wanted: impl Fn() -> bool,
+) -> Result<T, SearchError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, SearchError>>,
+{
+    let mut attempts = 1;
+    loop {
+        let error = match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        let Some(mut remaining_ms) = policy.delay_ms(&error, attempts) else {
+            return Err(error);
+        };
+
+        while remaining_ms > 0 {
+            if !wanted() {
+                return Err(error);
+            }
+            on_wait(remaining_ms.div_ceil(1_000));
+            let step = remaining_ms.min(1_000);
+            TimeoutFuture::new(step as u32).await;
+            remaining_ms -= step;
+        }
+        if !wanted() {
+            return Err(error);
+        }
+        on_wait(0);
+        attempts += 1;
+    }
+}
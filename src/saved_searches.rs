@@ -0,0 +1,225 @@
+// Named search presets, persisted in localStorage and shareable as JSON files
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{build_search_query, export, storage, SearchFilters};
+
+const SAVED_SEARCHES_STORAGE_KEY: &str = "oss-explorer.saved-searches";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SavedSearch {
+    pub name: String,
+    pub filters: SearchFilters,
+}
+
+/// Presets in the order they were first saved; names are unique, so saving or importing a name replaces it
+#[derive(Clone, Copy)]
+pub struct SavedSearches(RwSignal<Vec<SavedSearch>>);
+
+impl SavedSearches {
+    pub fn load() -> Self {
+        SavedSearches(RwSignal::new(
+            storage::load_json(SAVED_SEARCHES_STORAGE_KEY).unwrap_or_default(),
+        ))
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Vec<SavedSearch>)) {
+        self.0.update(|list| {
+            f(list);
+            storage::save_json(SAVED_SEARCHES_STORAGE_KEY, list);
+        });
+    }
+
+    /// Saves a search, replacing any existing one with the same name
+    pub fn save(&self, search: SavedSearch) {
+        self.update(
+            |list| match list.iter_mut().find(|s| s.name == search.name) {
+                Some(existing) => *existing = search,
+                None => list.push(search),
+            },
+        );
+    }
+
+    /// Renames a search; fails if another search already uses the new name
+    pub fn rename(&self, old_name: &str, new_name: &str) -> Result<(), String> {
+        if old_name != new_name
+            && self
+                .0
+                .with_untracked(|l| l.iter().any(|s| s.name == new_name))
+        {
+            return Err(format!(
+                "A saved search named \"{}\" already exists.",
+                new_name
+            ));
+        }
+        self.update(|list| {
+            if let Some(search) = list.iter_mut().find(|s| s.name == old_name) {
+                search.name = new_name.to_string();
+            }
+        });
+        Ok(())
+    }
+
+    pub fn delete(&self, name: &str) {
+        self.update(|list| list.retain(|s| s.name != name));
+    }
+
+    pub fn export_json(&self) -> String {
+        self.0
+            .with_untracked(serde_json::to_string_pretty)
+            .unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Merges searches from an exported JSON file, overwriting same-named ones; returns how many were imported
+    pub fn import_json(&self, json: &str) -> Result<usize, String> {
+        let imported: Vec<SavedSearch> = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid saved searches file: {}", e))?;
+        let count = imported.len();
+        for search in imported {
+            self.save(search);
+        }
+        Ok(count)
+    }
+}
+
+async fn read_file_text(file: web_sys::File) -> Result<String, String> {
+    wasm_bindgen_futures::JsFuture::from(file.text())
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .ok_or_else(|| "Could not read the selected file.".to_string())
+}
+
+#[component]
+pub fn SavedSearchesPanel(
+    /// The filters currently set in the search controls
+    current: impl Fn() -> SearchFilters + Copy + Send + Sync + 'static,
+    on_apply: impl Fn(SearchFilters) + Copy + Send + Sync + 'static,
+) -> impl IntoView {
+    let saved = expect_context::<SavedSearches>();
+    let (name_input, set_name_input) = signal(String::new());
+    let (message, set_message) = signal(Option::<String>::None);
+
+    let save_current = move |_| {
+        let name = name_input.get_untracked().trim().to_string();
+        if name.is_empty() {
+            set_message.set(Some("Enter a name for this search.".to_string()));
+            return;
+        }
+        saved.save(SavedSearch {
+            name: name.clone(),
+            filters: SearchFilters {
+                page: 1,
+                ..current()
+            },
+        });
+        set_name_input.set(String::new());
+        set_message.set(Some(format!("Saved \"{}\".", name)));
+    };
+
+    let rename = move |old_name: String| {
+        let new_name = web_sys::window()
+            .and_then(|w| {
+                w.prompt_with_message_and_default("Rename saved search", &old_name)
+                    .ok()
+                    .flatten()
+            })
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+        if let Some(new_name) = new_name {
+            set_message.set(saved.rename(&old_name, &new_name).err());
+        }
+    };
+
+    let export_all = move |_| {
+        if let Err(e) = export::save_file(
+            "oss-explorer-saved-searches.json",
+            "application/json",
+            &saved.export_json(),
+        ) {
+            set_message.set(Some(e));
+        }
+    };
+
+    let import = move |ev: web_sys::Event| {
+        use wasm_bindgen::JsCast;
+
+        let Some(input) = ev
+            .target()
+            .and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok())
+        else {
+            return;
+        };
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // Clear the input so selecting the same file again still fires a change event
+        input.set_value("");
+
+        leptos::task::spawn_local(async move {
+            let result = read_file_text(file)
+                .await
+                .and_then(|text| saved.import_json(&text));
+            set_message.set(Some(match result {
+                Ok(count) => format!("Imported {} saved searches.", count),
+                Err(e) => e,
+            }));
+        });
+    };
+
+    view! {
+        <div class="saved-searches">
+            <div class="filter-group saved-search-form">
+                <label>"Save current search as:"</label>
+                <input
+                    type="text"
+                    placeholder="e.g. New Rust CLIs"
+                    prop:value=move || name_input.get()
+                    on:input=move |ev| set_name_input.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            save_current(());
+                        }
+                    }
+                />
+                <button class="toggle-btn" on:click=move |_| save_current(())>"Save"</button>
+                <button class="toggle-btn" on:click=export_all>"Export JSON"</button>
+                <label class="toggle-btn file-btn">
+                    "Import JSON"
+                    <input type="file" accept=".json,application/json" on:change=import/>
+                </label>
+            </div>
+
+            {move || message.get().map(|m| view! { <p class="settings-hint">{m}</p> })}
+
+            {move || {
+                let list = saved.0.get();
+                (!list.is_empty()).then(|| view! {
+                    <ul class="saved-search-list">
+                        {list.into_iter().map(|search| {
+                            let summary = build_search_query(&search.filters);
+                            let filters = search.filters.clone();
+                            let rename_name = search.name.clone();
+                            let delete_name = search.name.clone();
+                            view! {
+                                <li>
+                                    <button
+                                        class="saved-search-name"
+                                        title="Apply this search"
+                                        on:click=move |_| on_apply(filters.clone())
+                                    >
+                                        {search.name.clone()}
+                                    </button>
+                                    <code class="saved-search-query">{summary}</code>
+                                    <button class="toggle-btn" on:click=move |_| rename(rename_name.clone())>"Rename"</button>
+                                    <button class="clear-btn" on:click=move |_| saved.delete(&delete_name)>"Delete"</button>
+                                </li>
+                            }
+                        }).collect::<Vec<_>>()}
+                    </ul>
+                })
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,148 @@
+// Curated "top repos" pages: `oss-explorer generate` runs a config of search presets at build
+// time and writes their results into dist/, and the app falls back to those snapshots while the
+// live API is rate limited
+
+use serde::{Deserialize, Serialize};
+
+use crate::{days_ago, Repository, SearchFilters, SearchResponse};
+
+/// Where the snapshots live, under dist/ and at the site root
+pub const SNAPSHOT_DIR: &str = "top";
+
+/// One curated page, as written in the generator's config
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotPreset {
+    /// The page's URL segment, e.g. `rust` for /top/rust
+    pub slug: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub filters: SearchFilters,
+    /// How many repositories to keep, fetched a full page per request
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(flatten)]
+    pub relative_dates: RelativeDates,
+}
+
+/// Date ranges counted back from the day a search runs, e.g. created in the last 30 days.
+/// They're resolved when the snapshot is generated so "this month" stays current, and kept so
+/// a later search over the same last 30 days still finds the snapshot.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct RelativeDates {
+    #[serde(default)]
+    pub created_within_days: Option<i64>,
+    #[serde(default)]
+    pub pushed_within_days: Option<i64>,
+}
+
+impl RelativeDates {
+    /// `filters` with these ranges starting as many days before today
+    pub fn resolve(&self, filters: &SearchFilters) -> SearchFilters {
+        let mut filters = filters.clone();
+        if let Some(days) = self.created_within_days {
+            filters.created_from = days_ago(days);
+        }
+        if let Some(days) = self.pushed_within_days {
+            filters.pushed_from = days_ago(days);
+        }
+        filters
+    }
+}
+
+fn default_limit() -> u32 {
+    100
+}
+
+/// A preset's results at generation time, written as `top/<slug>.json`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    /// RFC 3339 time the search was run
+    pub generated_at: String,
+    /// The search as run, with relative dates resolved
+    pub filters: SearchFilters,
+    #[serde(flatten)]
+    pub relative_dates: RelativeDates,
+    pub total_count: u32,
+    pub items: Vec<Repository>,
+}
+
+/// Every generated snapshot without its results, written as `top/index.json`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SnapshotIndex {
+    pub generated_at: String,
+    pub snapshots: Vec<SnapshotEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub filters: SearchFilters,
+    #[serde(flatten)]
+    pub relative_dates: RelativeDates,
+}
+
+impl SnapshotEntry {
+    fn covers(&self, filters: &SearchFilters) -> bool {
+        same_search(&self.relative_dates.resolve(&self.filters), filters)
+    }
+}
+
+impl Snapshot {
+    /// The page of `filters` this snapshot can stand in for: the same search (relative dates
+    /// counted from today), paged any way, as long as the page falls within the repositories it kept
+    pub fn page(&self, filters: &SearchFilters) -> Option<SearchResponse> {
+        if !same_search(&self.relative_dates.resolve(&self.filters), filters) {
+            return None;
+        }
+        let per_page = filters.per_page.max(1) as usize;
+        let start = (filters.page.max(1) as usize - 1) * per_page;
+        if start >= self.items.len() {
+            return None;
+        }
+        let end = (start + per_page).min(self.items.len());
+        Some(SearchResponse {
+            // Only what was kept can be paged through
+            total_count: self.total_count.min(self.items.len() as u32),
+            incomplete_results: false,
+            items: self.items[start..end].to_vec(),
+        })
+    }
+}
+
+/// Whether two filters run the same search, ignoring paging
+fn same_search(a: &SearchFilters, b: &SearchFilters) -> bool {
+    let unpaged = |filters: &SearchFilters| SearchFilters {
+        page: 1,
+        per_page: 0,
+        ..filters.clone()
+    };
+    unpaged(a) == unpaged(b)
+}
+
+/// The snapshot covering a search and its page of results, if the site has one
+pub async fn fallback(filters: &SearchFilters) -> Option<(Snapshot, SearchResponse)> {
+    let index: SnapshotIndex = fetch_json(&format!("/{}/index.json", SNAPSHOT_DIR)).await?;
+    let entry = index
+        .snapshots
+        .into_iter()
+        .find(|entry| entry.covers(filters))?;
+    let snapshot: Snapshot = fetch_json(&format!("/{}/{}.json", SNAPSHOT_DIR, entry.slug)).await?;
+    let page = snapshot.page(filters)?;
+    Some((snapshot, page))
+}
+
+// Missing snapshots are normal (none generated, or a host answering with the app page instead)
+async fn fetch_json<T: for<'de> Deserialize<'de>>(url: &str) -> Option<T> {
+    let response = reqwasm::http::Request::get(url).send().await.ok()?;
+    if !response.ok() {
+        return None;
+    }
+    response.json().await.ok()
+}
@@ -0,0 +1,119 @@
+// Server-side rendering for `oss-explorer serve`, and the browser's hydration of the pages it renders
+
+use leptos::prelude::*;
+
+use crate::{App, InitialSearch};
+
+// The script element carrying the server's search results over to the browser
+const INITIAL_SEARCH_ID: &str = "oss-explorer-initial-search";
+
+// Closes the part of index.html's <body> that a rendered page replaces (the loading spinner)
+#[cfg(feature = "ssr")]
+const APP_END_MARKER: &str = "<!-- /ssr:app -->";
+
+/// The search a page URL shows, for the search page only
+#[cfg(feature = "ssr")]
+pub fn search_filters_for(path_and_query: &str) -> Option<crate::SearchFilters> {
+    let url = leptos_router::location::RequestUrl::new(path_and_query)
+        .parse()
+        .ok()?;
+    (url.path() == "/").then(|| crate::SearchFilters::from_query_map(url.search_params()))
+}
+
+/// Renders the app at `path_and_query` into the index.html `shell`, with the page's title and
+/// Open Graph tags in its <head> and, for the search page, the results the server fetched
+#[cfg(feature = "ssr")]
+pub fn render_page(shell: &str, path_and_query: &str, initial: Option<InitialSearch>) -> String {
+    use futures::StreamExt;
+    use leptos::reactive::owner::Owner;
+    use leptos_meta::ServerMetaContext;
+    use leptos_router::location::RequestUrl;
+
+    let owner = Owner::new();
+    let (meta_context, meta_output) = ServerMetaContext::new();
+    let app_html = owner.with(|| {
+        provide_context(RequestUrl::new(path_and_query));
+        provide_context(meta_context);
+        if let Some(initial) = initial.clone() {
+            provide_context(initial);
+        }
+        view! { <App/> }.to_html()
+    });
+
+    // Hydration expects these globals, which a streaming integration would fill with resources
+    let mut head = String::from(
+        "<script>__RESOLVED_RESOURCES=[];__SERIALIZED_ERRORS=[];__INCOMPLETE_CHUNKS=[];</script>",
+    );
+    if let Some(json) = initial.and_then(|initial| serde_json::to_string(&initial).ok()) {
+        // `<` only appears inside JSON strings, where the escape keeps `</script>` out of the page
+        head.push_str(&format!(
+            r#"<script id="{}" type="application/json">{}</script>"#,
+            INITIAL_SEARCH_ID,
+            json.replace('<', "\\u003c")
+        ));
+    }
+    // leptos_meta puts the <title> before this marker and the meta tags after it
+    head.push_str("<!--HEAD-->");
+
+    let page = fill_shell(shell, &head, &app_html);
+    let page = futures::executor::block_on(async {
+        meta_output
+            .inject_meta_context(futures::stream::iter([page]))
+            .await
+            .collect::<String>()
+            .await
+    });
+    drop(owner);
+    page
+}
+
+/// Adds `head` to the end of the shell's <head> and puts the rendered app first in its <body>,
+/// dropping the static <title> in favour of the page's own
+#[cfg(feature = "ssr")]
+fn fill_shell(shell: &str, head: &str, app_html: &str) -> String {
+    let mut shell = shell.to_string();
+    if let (Some(start), Some(end)) = (shell.find("<title>"), shell.find("</title>")) {
+        shell.replace_range(start..end + "</title>".len(), "");
+    }
+
+    let head_end = shell.find("</head>").unwrap_or(0);
+    let body_start = shell[head_end..]
+        .find("<body")
+        .and_then(|i| {
+            shell[head_end + i..]
+                .find('>')
+                .map(|j| head_end + i + j + 1)
+        })
+        .unwrap_or(shell.len());
+    // The app must be the body's first child for hydration to find it
+    let app_end = shell
+        .find(APP_END_MARKER)
+        .map(|i| i + APP_END_MARKER.len())
+        .unwrap_or(body_start);
+
+    format!(
+        "{}{}{}{}{}",
+        &shell[..head_end],
+        head,
+        &shell[head_end..body_start],
+        app_html,
+        &shell[app_end..]
+    )
+}
+
+/// Hydrates a page the server rendered, reusing the search results it embedded
+#[cfg(feature = "hydrate")]
+pub fn hydrate() {
+    let initial = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.get_element_by_id(INITIAL_SEARCH_ID))
+        .and_then(|el| el.text_content())
+        .and_then(|json| serde_json::from_str::<InitialSearch>(&json).ok());
+
+    leptos::mount::hydrate_body(move || {
+        if let Some(initial) = initial {
+            provide_context(initial);
+        }
+        view! { <App/> }
+    });
+}
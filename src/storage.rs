@@ -0,0 +1,38 @@
+// Thin wrappers around the browser's localStorage
+
+fn local_storage() -> Option<web_sys::Storage> {
+    // Server-side rendering has no browser, and so no stored settings
+    if cfg!(not(target_arch = "wasm32")) {
+        return None;
+    }
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+/// Reads a value from localStorage, returning None if storage is unavailable
+pub fn get_item(key: &str) -> Option<String> {
+    local_storage().and_then(|s| s.get_item(key).ok().flatten())
+}
+
+/// Writes a value to localStorage, silently ignoring unavailable storage
+pub fn set_item(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(key, value);
+    }
+}
+
+pub fn remove_item(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
+
+/// Reads and deserializes a JSON value, treating unreadable data as absent
+pub fn load_json<T: serde::de::DeserializeOwned>(key: &str) -> Option<T> {
+    get_item(key).and_then(|raw| serde_json::from_str(&raw).ok())
+}
+
+pub fn save_json<T: serde::Serialize>(key: &str, value: &T) {
+    if let Ok(raw) = serde_json::to_string(value) {
+        set_item(key, &raw);
+    }
+}
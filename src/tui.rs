@@ -0,0 +1,729 @@
+// Interactive terminal explorer: `oss-explorer tui`, the web app's results table driven by the keyboard
+
+use std::io;
+use std::process::{Command, ExitCode, Stdio};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{
+    Block, Cell, Clear, List, ListState, Paragraph, Row, Table, TableState, Wrap,
+};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::backend::github::MAX_RESULTS;
+use crate::cli::{fetch, table_row, SearchArgs, TABLE_COLUMNS};
+use crate::{
+    build_search_query, calculate_total_pages, format_number, ArchivedFilter, ForkFilter,
+    SearchFilters, SearchResult, SortBy, SortOrder, LANGUAGES, STAR_RANGES,
+};
+
+const SORT_OPTIONS: &[(&str, SortBy, SortOrder)] = &[
+    ("Most stars", SortBy::Stars, SortOrder::Desc),
+    ("Fewest stars", SortBy::Stars, SortOrder::Asc),
+    ("Most forks", SortBy::Forks, SortOrder::Desc),
+    ("Fewest forks", SortBy::Forks, SortOrder::Asc),
+    ("Most help-wanted issues", SortBy::Issues, SortOrder::Desc),
+    ("Fewest help-wanted issues", SortBy::Issues, SortOrder::Asc),
+    ("Newest", SortBy::Created, SortOrder::Desc),
+    ("Oldest", SortBy::Created, SortOrder::Asc),
+    ("Recently updated", SortBy::Updated, SortOrder::Desc),
+    ("Least recently updated", SortBy::Updated, SortOrder::Asc),
+];
+
+const FORK_OPTIONS: &[(&str, ForkFilter)] = &[
+    ("All Repos", ForkFilter::All),
+    ("Original Only", ForkFilter::OriginalOnly),
+    ("Forks Only", ForkFilter::ForksOnly),
+];
+
+const ARCHIVED_OPTIONS: &[(&str, ArchivedFilter)] = &[
+    ("Active Only", ArchivedFilter::ActiveOnly),
+    ("All (incl. Archived)", ArchivedFilter::All),
+    ("Archived Only", ArchivedFilter::ArchivedOnly),
+];
+
+const HELP: &str = "↑↓ select  Enter open  / search  l language  s stars  o sort  f forks  a archived  n/p page  r refresh  q quit";
+
+/// A filter chosen from a popup list
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Panel {
+    Language,
+    Stars,
+    Sort,
+    Forks,
+    Archived,
+}
+
+impl Panel {
+    fn title(self) -> &'static str {
+        match self {
+            Panel::Language => " Language ",
+            Panel::Stars => " Stars ",
+            Panel::Sort => " Sort by ",
+            Panel::Forks => " Repository Type ",
+            Panel::Archived => " Status ",
+        }
+    }
+
+    fn labels(self) -> Vec<&'static str> {
+        match self {
+            Panel::Language => LANGUAGES.to_vec(),
+            Panel::Stars => STAR_RANGES.iter().map(|(label, _)| *label).collect(),
+            Panel::Sort => SORT_OPTIONS.iter().map(|(label, ..)| *label).collect(),
+            Panel::Forks => FORK_OPTIONS.iter().map(|(label, _)| *label).collect(),
+            Panel::Archived => ARCHIVED_OPTIONS.iter().map(|(label, _)| *label).collect(),
+        }
+    }
+
+    /// The option matching the current filters; a custom value (e.g. `--min-stars 10..50`)
+    /// selects the first
+    fn current(self, filters: &SearchFilters) -> usize {
+        match self {
+            Panel::Language => LANGUAGES.iter().position(|&l| l == filters.language),
+            Panel::Stars => STAR_RANGES
+                .iter()
+                .position(|(_, value)| *value == filters.min_stars),
+            Panel::Sort => SORT_OPTIONS
+                .iter()
+                .position(|(_, by, order)| *by == filters.sort_by && *order == filters.sort_order),
+            Panel::Forks => FORK_OPTIONS
+                .iter()
+                .position(|(_, f)| *f == filters.fork_filter),
+            Panel::Archived => ARCHIVED_OPTIONS
+                .iter()
+                .position(|(_, f)| *f == filters.archived_filter),
+        }
+        .unwrap_or(0)
+    }
+
+    fn apply(self, index: usize, filters: &mut SearchFilters) {
+        match self {
+            Panel::Language => filters.language = LANGUAGES[index].to_string(),
+            Panel::Stars => filters.min_stars = STAR_RANGES[index].1.to_string(),
+            Panel::Sort => {
+                let (_, by, order) = SORT_OPTIONS[index];
+                filters.sort_by = by;
+                filters.sort_order = order;
+            }
+            Panel::Forks => filters.fork_filter = FORK_OPTIONS[index].1,
+            Panel::Archived => filters.archived_filter = ARCHIVED_OPTIONS[index].1,
+        }
+    }
+}
+
+/// What the event loop should do after a key press
+enum Action {
+    None,
+    Search,
+    Open(String),
+    Quit,
+}
+
+struct Explorer {
+    search: SearchArgs,
+    result: Option<SearchResult>,
+    error: Option<String>,
+    loading: bool,
+    table: TableState,
+    /// The open filter popup and its highlighted option
+    panel: Option<(Panel, ListState)>,
+    /// The query being typed, while the search box has focus
+    query_input: Option<String>,
+    /// A one-off message for the status line, e.g. which URL was opened
+    status: Option<String>,
+}
+
+/// Runs the explorer until the user quits, restoring the terminal even if it fails
+pub fn run(search: SearchArgs) -> ExitCode {
+    // Not a terminal (e.g. piped input) is reported rather than panicking
+    let result = ratatui::try_init().and_then(|mut terminal| {
+        let result = Explorer::new(search).run(&mut terminal);
+        ratatui::restore();
+        result
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl Explorer {
+    fn new(search: SearchArgs) -> Self {
+        Explorer {
+            search,
+            result: None,
+            error: None,
+            loading: false,
+            table: TableState::default(),
+            panel: None,
+            query_input: None,
+            status: None,
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        self.run_search(terminal)?;
+        loop {
+            terminal.draw(|frame| self.render(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.handle_key(key) {
+                Action::None => {}
+                Action::Search => self.run_search(terminal)?,
+                Action::Open(url) => {
+                    self.status = Some(match open_in_browser(&url) {
+                        Ok(()) => format!("Opened {}", url),
+                        Err(e) => format!("Couldn't open a browser: {}", e),
+                    })
+                }
+                Action::Quit => return Ok(()),
+            }
+        }
+    }
+
+    /// Searches with the current filters, drawing the loading state first since the request blocks
+    fn run_search(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        self.loading = true;
+        self.status = None;
+        terminal.draw(|frame| self.render(frame))?;
+        self.load();
+        Ok(())
+    }
+
+    /// Runs the search, replacing the results (or showing the error in their place)
+    fn load(&mut self) {
+        match fetch(&self.search) {
+            Ok(result) => {
+                let has_items = !result.response.items.is_empty();
+                self.result = Some(result);
+                self.error = None;
+                self.table.select(has_items.then_some(0));
+            }
+            Err(e) => {
+                self.result = None;
+                self.error = Some(e.to_string());
+                self.table.select(None);
+            }
+        }
+        self.loading = false;
+    }
+
+    fn total_pages(&self) -> u32 {
+        self.result
+            .as_ref()
+            .map(|r| {
+                calculate_total_pages(
+                    r.response.total_count,
+                    self.search.filters.per_page,
+                    Some(MAX_RESULTS),
+                )
+            })
+            .unwrap_or(0)
+            .max(1)
+    }
+
+    fn item_count(&self) -> usize {
+        self.result.as_ref().map_or(0, |r| r.response.items.len())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        if self.query_input.is_some() {
+            return self.handle_query_key(key);
+        }
+        if self.panel.is_some() {
+            return self.handle_panel_key(key);
+        }
+
+        let page = self.search.filters.page;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX),
+            KeyCode::Enter | KeyCode::Char('b') => {
+                let selected = self
+                    .table
+                    .selected()
+                    .and_then(|i| self.result.as_ref().and_then(|r| r.response.items.get(i)));
+                if let Some(repo) = selected {
+                    return Action::Open(repo.html_url.clone());
+                }
+            }
+            KeyCode::Char('/') => self.query_input = Some(self.search.filters.query.clone()),
+            KeyCode::Char('l') => self.open_panel(Panel::Language),
+            KeyCode::Char('s') => self.open_panel(Panel::Stars),
+            KeyCode::Char('o') => self.open_panel(Panel::Sort),
+            KeyCode::Char('f') => self.open_panel(Panel::Forks),
+            KeyCode::Char('a') => self.open_panel(Panel::Archived),
+            KeyCode::Char('n') | KeyCode::Right | KeyCode::PageDown
+                if page < self.total_pages() =>
+            {
+                self.search.filters.page += 1;
+                return Action::Search;
+            }
+            KeyCode::Char('p') | KeyCode::Left | KeyCode::PageUp if page > 1 => {
+                self.search.filters.page -= 1;
+                return Action::Search;
+            }
+            KeyCode::Char('r') => return Action::Search,
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn handle_query_key(&mut self, key: KeyEvent) -> Action {
+        let Some(input) = self.query_input.as_mut() else {
+            return Action::None;
+        };
+        match key.code {
+            KeyCode::Enter => {
+                self.search.filters.query = input.trim().to_string();
+                self.search.filters.page = 1;
+                self.query_input = None;
+                return Action::Search;
+            }
+            KeyCode::Esc => self.query_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn handle_panel_key(&mut self, key: KeyEvent) -> Action {
+        let Some((panel, list)) = self.panel.as_mut() else {
+            return Action::None;
+        };
+        let last = panel.labels().len() - 1;
+        let selected = list.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => list.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down | KeyCode::Char('j') => list.select(Some((selected + 1).min(last))),
+            KeyCode::Enter => {
+                panel.apply(selected, &mut self.search.filters);
+                self.search.filters.page = 1;
+                self.panel = None;
+                return Action::Search;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.panel = None,
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn open_panel(&mut self, panel: Panel) {
+        let list = ListState::default().with_selected(Some(panel.current(&self.search.filters)));
+        self.panel = Some((panel, list));
+    }
+
+    /// Moves the highlighted row by `delta`, clamped to the rows on this page
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.item_count();
+        if count == 0 {
+            return;
+        }
+        let current = self.table.selected().unwrap_or(0) as isize;
+        let next = current.saturating_add(delta).clamp(0, count as isize - 1);
+        self.table.select(Some(next as usize));
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [header, body, status, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        self.render_header(frame, header);
+        self.render_results(frame, body);
+        frame.render_widget(Paragraph::new(self.status_line()), status);
+        frame.render_widget(
+            Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+            help,
+        );
+        if let Some((panel, list)) = self.panel.as_mut() {
+            render_panel(frame, *panel, list);
+        }
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" OSS Explorer ");
+        let line = match &self.query_input {
+            Some(input) => Line::from(format!("Search: {}", input)),
+            None => {
+                let filters = &self.search.filters;
+                let sort = SORT_OPTIONS
+                    .iter()
+                    .find(|(_, by, order)| *by == filters.sort_by && *order == filters.sort_order)
+                    .map_or("", |(label, ..)| *label);
+                Line::from(format!("{}  ·  {}", build_search_query(filters), sort))
+            }
+        };
+        if let Some(input) = &self.query_input {
+            // Place the cursor after the typed text, inside the border
+            let x = area.x + 1 + "Search: ".len() as u16 + input.chars().count() as u16;
+            frame.set_cursor_position(Position::new(
+                x.min(area.right().saturating_sub(2)),
+                area.y + 1,
+            ));
+        }
+        frame.render_widget(Paragraph::new(line).block(block), area);
+    }
+
+    fn render_results(&mut self, frame: &mut Frame, area: Rect) {
+        let title = match &self.result {
+            Some(result) => format!(
+                " {} repositories found ",
+                format_number(result.response.total_count)
+            ),
+            None => " Results ".to_string(),
+        };
+        let block = Block::bordered().title(title);
+
+        if let Some(error) = &self.error {
+            let message = Paragraph::new(error.as_str())
+                .style(Style::default().fg(Color::Red))
+                .wrap(Wrap { trim: true })
+                .block(block);
+            frame.render_widget(message, area);
+            return;
+        }
+        let Some(result) = &self.result else {
+            frame.render_widget(Paragraph::new("Searching…").block(block), area);
+            return;
+        };
+        if result.response.items.is_empty() {
+            let message =
+                Paragraph::new("No repositories found. Try adjusting your filters.").block(block);
+            frame.render_widget(message, area);
+            return;
+        }
+
+        let header = Row::new(TABLE_COLUMNS.iter().enumerate().map(|(i, heading)| {
+            aligned_cell(heading.to_string(), i)
+                .style(Style::default().add_modifier(Modifier::BOLD))
+        }));
+        let rows = result.response.items.iter().map(|repo| {
+            Row::new(
+                table_row(repo)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, cell)| aligned_cell(cell, i)),
+            )
+        });
+        let widths = [
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+
+    fn status_line(&self) -> String {
+        if self.loading {
+            return "Searching…".to_string();
+        }
+        if let Some(status) = &self.status {
+            return status.clone();
+        }
+        let mut parts = vec![format!(
+            "Page {} of {}",
+            self.search.filters.page,
+            self.total_pages()
+        )];
+        if let Some(rate_limit) = self.result.as_ref().and_then(|r| r.rate_limit.as_ref()) {
+            parts.push(format!(
+                "{}/{} searches left",
+                rate_limit.remaining, rate_limit.limit
+            ));
+        }
+        if self.search.fixture.is_some() {
+            parts.push("offline fixture".to_string());
+        }
+        parts.join("  ·  ")
+    }
+}
+
+/// A table cell, right-aligned for the numeric columns as in the plain-text table
+fn aligned_cell<'a>(content: String, column: usize) -> Cell<'a> {
+    let text = Text::from(content);
+    if (2..=4).contains(&column) {
+        Cell::from(text.alignment(Alignment::Right))
+    } else {
+        Cell::from(text)
+    }
+}
+
+fn render_panel(frame: &mut Frame, panel: Panel, list: &mut ListState) {
+    let labels = panel.labels();
+    let area = frame.area();
+    let width = 34.min(area.width);
+    let height = (labels.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let widget = List::new(labels)
+        .block(Block::bordered().title(panel.title()))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    frame.render_widget(Clear, popup);
+    frame.render_stateful_widget(widget, popup, list);
+}
+
+/// Hands a URL to the platform's default browser without waiting for it
+fn open_in_browser(url: &str) -> io::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = Command::new("explorer");
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+    use crate::cli::OutputFormat;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/search-rust.json");
+
+    /// An explorer showing the recorded `language:Rust` search
+    fn explorer() -> Explorer {
+        let mut explorer = Explorer::new(SearchArgs {
+            filters: SearchFilters {
+                language: "Rust".to_string(),
+                ..SearchFilters::default()
+            },
+            format: OutputFormat::Table,
+            api_url: String::new(),
+            token: None,
+            fixture: Some(FIXTURE.to_string()),
+        });
+        explorer.load();
+        explorer
+    }
+
+    fn press(explorer: &mut Explorer, code: KeyCode) -> Action {
+        explorer.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// The rendered screen, one string per row
+    fn render(explorer: &mut Explorer, width: u16, height: u16) -> Vec<String> {
+        screen(&draw(explorer, width, height))
+    }
+
+    fn draw(explorer: &mut Explorer, width: u16, height: u16) -> Terminal<TestBackend> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| explorer.render(frame)).unwrap();
+        terminal
+    }
+
+    fn screen(terminal: &Terminal<TestBackend>) -> Vec<String> {
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn loads_the_fixture_with_the_first_row_selected() {
+        let explorer = explorer();
+        assert_eq!(explorer.item_count(), 3);
+        assert_eq!(explorer.table.selected(), Some(0));
+        assert_eq!(explorer.error, None);
+    }
+
+    #[test]
+    fn navigation_stays_within_the_page() {
+        let mut explorer = explorer();
+        press(&mut explorer, KeyCode::Up);
+        assert_eq!(explorer.table.selected(), Some(0));
+        press(&mut explorer, KeyCode::Down);
+        press(&mut explorer, KeyCode::Char('j'));
+        press(&mut explorer, KeyCode::Down);
+        assert_eq!(explorer.table.selected(), Some(2));
+        press(&mut explorer, KeyCode::Char('g'));
+        assert_eq!(explorer.table.selected(), Some(0));
+        press(&mut explorer, KeyCode::End);
+        assert_eq!(explorer.table.selected(), Some(2));
+        press(&mut explorer, KeyCode::Char('k'));
+        assert_eq!(explorer.table.selected(), Some(1));
+    }
+
+    #[test]
+    fn paging_stops_at_the_only_page() {
+        let mut explorer = explorer();
+        assert!(matches!(
+            press(&mut explorer, KeyCode::Char('n')),
+            Action::None
+        ));
+        assert!(matches!(
+            press(&mut explorer, KeyCode::Char('p')),
+            Action::None
+        ));
+        assert_eq!(explorer.search.filters.page, 1);
+    }
+
+    #[test]
+    fn opens_the_selected_repository() {
+        let mut explorer = explorer();
+        press(&mut explorer, KeyCode::Down);
+        match press(&mut explorer, KeyCode::Enter) {
+            Action::Open(url) => assert_eq!(url, "https://github.com/denoland/deno"),
+            _ => panic!("Enter should open the selected repository"),
+        }
+    }
+
+    #[test]
+    fn sort_panel_applies_the_chosen_order() {
+        let mut explorer = explorer();
+        explorer.search.filters.page = 2;
+        press(&mut explorer, KeyCode::Char('o'));
+        assert!(matches!(explorer.panel, Some((Panel::Sort, _))));
+        press(&mut explorer, KeyCode::Down);
+        press(&mut explorer, KeyCode::Down);
+        assert!(matches!(
+            press(&mut explorer, KeyCode::Enter),
+            Action::Search
+        ));
+        assert!(explorer.panel.is_none());
+        assert_eq!(explorer.search.filters.sort_by, SortBy::Forks);
+        assert_eq!(explorer.search.filters.sort_order, SortOrder::Desc);
+        assert_eq!(explorer.search.filters.page, 1);
+    }
+
+    #[test]
+    fn escape_backs_out_of_a_panel_and_the_search_box() {
+        let mut explorer = explorer();
+        press(&mut explorer, KeyCode::Char('o'));
+        press(&mut explorer, KeyCode::Down);
+        assert!(matches!(press(&mut explorer, KeyCode::Esc), Action::None));
+        assert!(explorer.panel.is_none());
+        assert_eq!(explorer.search.filters.sort_by, SortBy::Stars);
+
+        press(&mut explorer, KeyCode::Char('/'));
+        press(&mut explorer, KeyCode::Char('x'));
+        assert!(matches!(press(&mut explorer, KeyCode::Esc), Action::None));
+        assert_eq!(explorer.query_input, None);
+        assert_eq!(explorer.search.filters.query, "");
+
+        // With nothing left to back out of, Esc quits
+        assert!(matches!(press(&mut explorer, KeyCode::Esc), Action::Quit));
+    }
+
+    #[test]
+    fn typed_query_searches_from_the_first_page() {
+        let mut explorer = explorer();
+        explorer.search.filters.page = 3;
+        press(&mut explorer, KeyCode::Char('/'));
+        for c in " cli ".chars() {
+            press(&mut explorer, KeyCode::Char(c));
+        }
+        assert!(matches!(
+            press(&mut explorer, KeyCode::Enter),
+            Action::Search
+        ));
+        assert_eq!(explorer.search.filters.query, "cli");
+        assert_eq!(explorer.search.filters.page, 1);
+    }
+
+    #[test]
+    fn renders_the_results_table() {
+        let mut explorer = explorer();
+        let screen = render(&mut explorer, 100, 12);
+        assert!(screen[0].contains("OSS Explorer"));
+        assert!(screen[1].contains("language:Rust"));
+        assert!(screen[1].contains("Most stars"));
+        assert!(screen[3].contains("3 repositories found"));
+        assert!(screen[4].contains("Repository"));
+        assert!(screen[5].contains("rust-lang/rust"));
+        assert!(screen[5].contains("101.2K"));
+        assert!(screen[6].contains("denoland/deno"));
+        assert!(screen[7].contains("BurntSushi/ripgrep"));
+        assert!(screen[10].contains("Page 1 of 1"));
+        assert!(screen[10].contains("offline fixture"));
+        assert!(screen[11].starts_with("↑↓ select"));
+    }
+
+    #[test]
+    fn renders_the_sort_panel_over_the_results() {
+        let mut explorer = explorer();
+        press(&mut explorer, KeyCode::Char('o'));
+        let screen = render(&mut explorer, 100, 20);
+        assert!(screen.iter().any(|row| row.contains("Sort by")));
+        assert!(screen.iter().any(|row| row.contains("> Most stars")));
+    }
+
+    #[test]
+    fn search_cursor_stays_inside_the_header() {
+        let mut explorer = explorer();
+        press(&mut explorer, KeyCode::Char('/'));
+        for c in "tokio".chars() {
+            press(&mut explorer, KeyCode::Char(c));
+        }
+
+        // After the typed text when it fits
+        let mut terminal = draw(&mut explorer, 40, 8);
+        assert!(screen(&terminal)[1].starts_with("│Search: tokio "));
+        assert_eq!(
+            terminal.get_cursor_position().unwrap(),
+            Position::new(14, 1)
+        );
+
+        // On the last column inside the border when it doesn't
+        let mut terminal = draw(&mut explorer, 12, 8);
+        assert_eq!(screen(&terminal)[1], "│Search: to│");
+        assert_eq!(
+            terminal.get_cursor_position().unwrap(),
+            Position::new(10, 1)
+        );
+
+        // At the edge of a header too narrow for its border
+        let mut terminal = draw(&mut explorer, 1, 8);
+        assert_eq!(terminal.get_cursor_position().unwrap(), Position::new(0, 1));
+    }
+}
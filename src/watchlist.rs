@@ -0,0 +1,265 @@
+// Locally starred repositories, persisted in localStorage
+
+use leptos::prelude::*;
+use leptos_router::components::A;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::Forge;
+use crate::{format_date, format_number, repo_detail_url, storage, ForgeBackends, Repository};
+
+const WATCHLIST_STORAGE_KEY: &str = "oss-explorer.watchlist";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WatchedRepo {
+    /// Snapshot taken when the repository was starred; deltas are measured against it
+    pub saved: Repository,
+    pub saved_at: String,
+    /// Result of the most recent refresh, if any
+    #[serde(default)]
+    pub latest: Option<Repository>,
+    #[serde(default)]
+    pub refreshed_at: Option<String>,
+}
+
+impl WatchedRepo {
+    fn star_delta(&self) -> Option<i64> {
+        self.latest
+            .as_ref()
+            .map(|l| l.stargazers_count as i64 - self.saved.stargazers_count as i64)
+    }
+
+    fn issue_delta(&self) -> Option<i64> {
+        self.latest
+            .as_ref()
+            .map(|l| l.open_issues_count as i64 - self.saved.open_issues_count as i64)
+    }
+
+    fn current(&self) -> &Repository {
+        self.latest.as_ref().unwrap_or(&self.saved)
+    }
+
+    /// Whether this is the repository `full_name` on `forge`; mirrors on other forges share names
+    fn is(&self, forge: Forge, full_name: &str) -> bool {
+        self.saved.forge.unwrap_or_default() == forge && self.saved.full_name == full_name
+    }
+}
+
+/// Starred repositories in the order they were starred, behind the star buttons and the watchlist page
+#[derive(Clone, Copy)]
+pub struct Watchlist(RwSignal<Vec<WatchedRepo>>);
+
+impl Watchlist {
+    pub fn load() -> Self {
+        let mut list: Vec<WatchedRepo> =
+            storage::load_json(WATCHLIST_STORAGE_KEY).unwrap_or_default();
+        // Entries saved before searches spanned forges have no forge; they're all GitHub's
+        if list.iter().any(|w| w.saved.forge.is_none()) {
+            for watched in &mut list {
+                watched.saved.forge.get_or_insert(Forge::Github);
+            }
+            storage::save_json(WATCHLIST_STORAGE_KEY, &list);
+        }
+        Watchlist(RwSignal::new(list))
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Vec<WatchedRepo>)) {
+        self.0.update(|list| {
+            f(list);
+            storage::save_json(WATCHLIST_STORAGE_KEY, list);
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.with(|list| list.len())
+    }
+
+    pub fn contains(&self, forge: Forge, full_name: &str) -> bool {
+        self.0
+            .with(|list| list.iter().any(|w| w.is(forge, full_name)))
+    }
+
+    pub fn toggle(&self, repo: &Repository) {
+        let forge = repo.forge.unwrap_or_default();
+        if self.contains(forge, &repo.full_name) {
+            self.remove(forge, &repo.full_name);
+        } else {
+            let watched = WatchedRepo {
+                saved: Repository {
+                    forge: Some(forge),
+                    ..repo.clone()
+                },
+                saved_at: chrono::Utc::now().to_rfc3339(),
+                latest: None,
+                refreshed_at: None,
+            };
+            self.update(|list| list.push(watched));
+        }
+    }
+
+    pub fn remove(&self, forge: Forge, full_name: &str) {
+        self.update(|list| list.retain(|w| !w.is(forge, full_name)));
+    }
+
+    fn set_latest(&self, forge: Forge, full_name: &str, latest: Repository) {
+        let now = chrono::Utc::now().to_rfc3339();
+        self.update(|list| {
+            if let Some(watched) = list.iter_mut().find(|w| w.is(forge, full_name)) {
+                watched.latest = Some(latest);
+                watched.refreshed_at = Some(now);
+            }
+        });
+    }
+}
+
+fn format_delta(delta: Option<i64>) -> (String, &'static str) {
+    match delta {
+        Some(d) if d > 0 => (format!("+{}", d), "delta-up"),
+        Some(d) if d < 0 => (d.to_string(), "delta-down"),
+        Some(_) => ("±0".to_string(), "delta-none"),
+        None => (String::new(), "delta-none"),
+    }
+}
+
+/// Star toggle shown on each results row
+#[component]
+pub fn WatchToggle(repo: Repository) -> impl IntoView {
+    let watchlist = expect_context::<Watchlist>();
+    let forge = repo.forge.unwrap_or_default();
+    let full_name = repo.full_name.clone();
+    let watched = Memo::new(move |_| watchlist.contains(forge, &full_name));
+
+    view! {
+        <button
+            class="watch-toggle"
+            class:watched=move || watched.get()
+            title=move || if watched.get() { "Remove from watchlist" } else { "Add to watchlist" }
+            on:click=move |_| watchlist.toggle(&repo)
+        >
+            {move || if watched.get() { "★" } else { "☆" }}
+        </button>
+    }
+}
+
+#[component]
+pub fn WatchlistPage() -> impl IntoView {
+    let watchlist = expect_context::<Watchlist>();
+    let forge_backends = expect_context::<ForgeBackends>();
+    let (refreshing, set_refreshing) = signal(Option::<String>::None);
+    let (error, set_error) = signal(Option::<String>::None);
+
+    let refresh = move |_| {
+        // Each repository is refreshed from the forge it was saved from
+        let repos: Vec<(String, Forge)> = watchlist.0.with_untracked(|list| {
+            list.iter()
+                .map(|w| (w.saved.full_name.clone(), w.saved.forge.unwrap_or_default()))
+                .collect()
+        });
+        set_error.set(None);
+
+        leptos::task::spawn_local(async move {
+            let mut failures = Vec::new();
+            for (i, (name, forge)) in repos.iter().enumerate() {
+                set_refreshing.set(Some(format!("Refreshing {} of {}...", i + 1, repos.len())));
+                let backend = forge_backends.get_untracked(*forge);
+                match backend.fetch_repository(name).await {
+                    Ok(repo) => watchlist.set_latest(*forge, name, repo),
+                    Err(e) => failures.push(format!("{}: {}", name, e)),
+                }
+            }
+            if !failures.is_empty() {
+                set_error.set(Some(failures.join("; ")));
+            }
+            set_refreshing.set(None);
+        });
+    };
+
+    view! {
+        <div class="watchlist-page">
+            <div class="results-header">
+                <span class="count">
+                    {move || format!("{} saved repositories", watchlist.len())}
+                </span>
+                <button
+                    class="toggle-btn"
+                    on:click=refresh
+                    disabled=move || refreshing.get().is_some() || watchlist.len() == 0
+                >
+                    {move || refreshing.get().unwrap_or_else(|| "Refresh".to_string())}
+                </button>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <div class="error">
+                    <strong>"Refresh failed for "</strong>{e}
+                </div>
+            })}
+
+            <div class="results">
+                {move || {
+                    let list = watchlist.0.get();
+                    if list.is_empty() {
+                        view! {
+                            <div class="empty">"Your watchlist is empty. Use the ☆ next to a search result to save it here."</div>
+                        }.into_any()
+                    } else {
+                        view! {
+                            <table>
+                                <thead>
+                                    <tr>
+                                        <th>"Repository"</th>
+                                        <th>"Language"</th>
+                                        <th>"Stars"</th>
+                                        <th>"Issues"</th>
+                                        <th>"Saved"</th>
+                                        <th>"Refreshed"</th>
+                                        <th></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {list.into_iter().map(|watched| {
+                                        let current = watched.current().clone();
+                                        let (star_delta, star_class) = format_delta(watched.star_delta());
+                                        let (issue_delta, issue_class) = format_delta(watched.issue_delta());
+                                        let detail_url = repo_detail_url(&current);
+                                        let forge = watched.saved.forge.unwrap_or_default();
+                                        let full_name = watched.saved.full_name.clone();
+                                        view! {
+                                            <tr>
+                                                <td class="repo-cell">
+                                                    <A href=detail_url attr:class="repo-name">{current.full_name.clone()}</A>
+                                                    <p class="repo-description">{current.description.clone().unwrap_or_default()}</p>
+                                                </td>
+                                                <td>
+                                                    <span class="language-badge">
+                                                        {current.language.clone().unwrap_or_else(|| "Unknown".to_string())}
+                                                    </span>
+                                                </td>
+                                                <td class="stat">
+                                                    {format_number(current.stargazers_count)}
+                                                    <span class=format!("delta {}", star_class)>{star_delta}</span>
+                                                </td>
+                                                <td class="stat">
+                                                    {format_number(current.open_issues_count)}
+                                                    <span class=format!("delta {}", issue_class)>{issue_delta}</span>
+                                                </td>
+                                                <td class="date">{format_date(&watched.saved_at)}</td>
+                                                <td class="date">
+                                                    {watched.refreshed_at.as_deref().map(format_date).unwrap_or_else(|| "Never".to_string())}
+                                                </td>
+                                                <td>
+                                                    <button class="clear-btn" on:click=move |_| watchlist.remove(forge, &full_name)>
+                                                        "Remove"
+                                                    </button>
+                                                </td>
+                                            </tr>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </tbody>
+                            </table>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </div>
+    }
+}